@@ -36,12 +36,11 @@ impl Buttons {
     }
 
     pub fn new_button(&mut self, id: u32, x_pos: f32, y_pos: f32, width: f32, height: f32, btn_text: &str) -> u32 {
-        let new_id: u32;
-        if id == 0 {
-            new_id = self.get_highest_id();
+        let new_id: u32 = if id == 0 {
+            self.get_highest_id()
         } else {
-            new_id = id
-        }
+            id
+        };
         let font_size: u16 = (height * 0.33) as u16;
         let td = measure_text(btn_text, Some(&self.font), font_size, 1.0);
         let offset_x: f32 = (width - td.width) * 0.5;
@@ -61,17 +60,25 @@ impl Buttons {
         new_id
     }
 
+    pub fn set_text(&mut self, btn_id: u32, btn_text: &str) {
+        if let Some(btn) = self.btns.iter_mut().find(|btn| btn.id == btn_id) {
+            let td = measure_text(btn_text, Some(&self.font), btn.txt_size, 1.0);
+            btn.txt = btn_text.to_string();
+            btn.txt_x = btn.x + (btn.w - td.width) * 0.5;
+            btn.txt_y = btn.y + (btn.h - td.height) * 0.5 + td.offset_y;
+        }
+    }
+
     pub fn del_button(&mut self, btn_id: u32) {
-        let mut i: usize = 0;
-        for btn in self.btns.iter() {
-            if btn.id == btn_id {
-                self.btns.remove(i);
-                break;
-            }
-            i += 1;
+        if let Some(i) = self.btns.iter().position(|btn| btn.id == btn_id) {
+            self.btns.remove(i);
         }
     }
 
+    pub fn has_button(&self, btn_id: u32) -> bool {
+        self.btns.iter().any(|btn| btn.id == btn_id)
+    }
+
     pub fn reset(&mut self) {
         self.btns.clear();
     }
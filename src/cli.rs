@@ -0,0 +1,241 @@
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::time::SystemTime;
+
+use sudoku_engine::board::SudokuBoard;
+use sudoku_engine::formats::{parse_puzzles, write_puzzle, Format};
+use sudoku_engine::logic::{generate, grade, new_seed, Difficulty};
+use sudoku_engine::rng::Rng;
+use sudoku_engine::savegame::SaveGame;
+
+use crate::constants::GAME_TITLE;
+
+struct Options {
+    format: Format,
+    givens: bool,
+    difficulty: Difficulty,
+    count: usize,
+    limit: usize,
+    seed: Option<u64>,
+    file_name: Option<String>,
+}
+
+pub fn run(args: &[String]) -> i32 {
+
+    let result = match args[0].as_str() {
+        "solve" => parse_options(&args[1..], &["--format"]).and_then(|o| solve(&o)),
+        "generate" => parse_options(&args[1..], &["--format", "--difficulty", "--count", "--seed"]).and_then(|o| generate_puzzles(&o)),
+        "grade" => parse_options(&args[1..], &[]).and_then(|o| grade_puzzles(&o)),
+        "count-solutions" => parse_options(&args[1..], &["--limit"]).and_then(|o| count_solutions(&o)),
+        "convert" | "export" => parse_options(&args[1..], &["--format", "--givens"]).and_then(|o| convert(&o)),
+        "help" | "-h" | "--help" => {
+            print_usage();
+            Ok(())
+        }
+        cmd => Err(format!("Unknown command '{}', see '{}'.", cmd, usage_command())),
+    };
+
+    match result {
+        Ok(_) => 0,
+        Err(err) => {
+            eprintln!("{}", err);
+            1
+        }
+    }
+
+}
+
+fn print_usage() {
+    println!("{} - without a command the game starts with a window", GAME_TITLE);
+    println!();
+    println!("Commands, a missing file or '-' reads the puzzles from stdin:");
+    println!("  solve [--format line|sdk|ascii] [file]");
+    println!("      prints the solution of every puzzle");
+    println!("  generate [--difficulty easy|moderate|hard] [--count n] [--seed n] [--format line|sdk|ascii]");
+    println!("      prints new puzzles with exactly one solution, the same seed gives the same puzzles");
+    println!("  grade [file]");
+    println!("      prints every puzzle with difficulty, score and the hardest technique");
+    println!("  count-solutions [--limit n] [file]");
+    println!("      prints the number of solutions of every puzzle, counting stops at the limit");
+    println!("  convert [--givens] [--format line|sdk|ascii] [file]");
+    println!("      prints the board of a savegame or all puzzles of a puzzle file,");
+    println!("      --givens prints only the puzzle without the numbers of the player");
+    println!("  export");
+    println!("      the same as convert");
+    println!("  help");
+    println!("      prints this text");
+}
+
+fn usage_command() -> String {
+    let program = std::env::args().next().unwrap_or_default();
+    format!("{} help", program)
+}
+
+fn parse_options(args: &[String], allowed: &[&str]) -> Result<Options, String> {
+
+    let mut options = Options {
+        format: Format::Line,
+        givens: false,
+        difficulty: Difficulty::Moderate,
+        count: 1,
+        limit: 1000,
+        seed: None,
+        file_name: None,
+    };
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+
+        if arg.starts_with("--") && !allowed.contains(&arg.as_str()) {
+            return Err(format!("Unknown argument '{}'.", arg));
+        }
+
+        match arg.as_str() {
+            "--givens" => options.givens = true,
+            "--format" => {
+                let name = iter.next().ok_or("--format needs a format")?;
+                options.format = Format::from_name(name).ok_or(format!("Unknown format '{}'.", name))?;
+            }
+            "--difficulty" => {
+                let name = iter.next().ok_or("--difficulty needs a difficulty")?;
+                options.difficulty = Difficulty::from_name(name).ok_or(format!("Unknown difficulty '{}'.", name))?;
+            }
+            "--count" => {
+                let count = iter.next().ok_or("--count needs a number")?;
+                options.count = count.parse::<usize>().map_err(|_| format!("'{}' is no count.", count))?;
+            }
+            "--seed" => {
+                let seed = iter.next().ok_or("--seed needs a number")?;
+                options.seed = Some(seed.parse::<u64>().map_err(|_| format!("'{}' is no seed.", seed))?);
+            }
+            "--limit" => {
+                let limit = iter.next().ok_or("--limit needs a number")?;
+                options.limit = limit.parse::<usize>().ok().filter(|l| *l > 0).ok_or(format!("'{}' is no limit.", limit))?;
+            }
+            _ if options.file_name.is_none() => options.file_name = Some(arg.clone()),
+            _ => return Err(format!("Unknown argument '{}'.", arg)),
+        }
+
+    }
+
+    Ok(options)
+
+}
+
+fn output(text: &str) -> Result<(), String> {
+    // a closed pipe ends the command with an error instead of a panic
+    io::stdout().write_all(text.as_bytes()).map_err(|err| format!("Can't write to stdout: {}", err))
+}
+
+fn read_boards(file_name: &Option<String>) -> Result<Vec<SudokuBoard>, String> {
+
+    let (name, text) = match file_name.as_deref() {
+        None | Some("-") => ("stdin", io::read_to_string(io::stdin()).map_err(|err| format!("Can't read stdin: {}", err))?),
+        Some(name) => (name, fs::read_to_string(name).map_err(|err| format!("Can't read {}: {}", name, err))?),
+    };
+
+    // a savegame has one board, a puzzle file maybe a lot of them
+    if let Ok(game) = SaveGame::parse(&text) {
+        let mut board = SudokuBoard::default();
+        board.restore_savegame(&game);
+        return Ok(vec![board]);
+    }
+
+    let puzzles = parse_puzzles(&text).map_err(|err| format!("Can't read {}: {}", name, err))?;
+    Ok(puzzles.iter().map(|grid| {
+        let mut board = SudokuBoard::default();
+        board.set_givens(grid);
+        board
+    }).collect())
+
+}
+
+fn solve(options: &Options) -> Result<(), String> {
+
+    let mut failed: usize = 0;
+
+    for (i, board) in read_boards(&options.file_name)?.iter().enumerate() {
+        match board.solution() {
+            Some(solution) => {
+                if board.givens().count_solutions(2) > 1 {
+                    eprintln!("Puzzle {} has more than one solution, this is the first one.", i + 1);
+                }
+                output(&write_puzzle(&solution.grid(), options.format))?;
+            }
+            None => {
+                eprintln!("Puzzle {} has no solution.", i + 1);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("{} puzzles without a solution.", failed));
+    }
+    Ok(())
+
+}
+
+fn generate_puzzles(options: &Options) -> Result<(), String> {
+
+    let sys_secs = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let mut rng = Rng::new(sys_secs);
+
+    // puzzles with a given seed use the following numbers as seeds too
+    let mut board = SudokuBoard::default();
+    for i in 0..options.count {
+        let seed = match options.seed {
+            Some(s) => s.checked_add(i as u64).ok_or(format!("The seed {} + {} is too big.", s, i))?,
+            None => new_seed(&mut rng),
+        };
+        generate(&mut board, options.difficulty, seed);
+        output(&write_puzzle(&board.grid(), options.format))?;
+    }
+
+    Ok(())
+
+}
+
+fn grade_puzzles(options: &Options) -> Result<(), String> {
+
+    for board in read_boards(&options.file_name)?.iter() {
+        let puzzle = write_puzzle(&board.givens().grid(), Format::Line);
+        let result = grade(board);
+        let technique = result.hardest.map_or("none", |t| t.name());
+        let difficulty = match board.givens().count_solutions(2) {
+            0 => "no solution",
+            1 => Difficulty::from_score(result.score).name(),
+            _ => "not unique",
+        };
+        output(&format!("{}\t{}\t{}\t{}\n", puzzle.trim_end(), difficulty, result.score, technique))?;
+    }
+
+    Ok(())
+
+}
+
+fn count_solutions(options: &Options) -> Result<(), String> {
+
+    for board in read_boards(&options.file_name)?.iter() {
+        let puzzle = write_puzzle(&board.givens().grid(), Format::Line);
+        output(&format!("{}\t{}\n", puzzle.trim_end(), board.givens().count_solutions(options.limit)))?;
+    }
+
+    Ok(())
+
+}
+
+fn convert(options: &Options) -> Result<(), String> {
+
+    for board in read_boards(&options.file_name)?.iter() {
+        let grid = if options.givens { board.givens().grid() } else { board.grid() };
+        output(&write_puzzle(&grid, options.format))?;
+    }
+
+    Ok(())
+
+}
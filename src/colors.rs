@@ -34,6 +34,20 @@ pub struct Colors {
     pub number_default: Color,
     pub number_editable: Color,
     pub number_notvalid: Color,
+    pub number_note: Color,
+    pub hint_cell: Color,
+    pub hint_target: Color,
+    pub hint_candidate: Color,
+    pub conflict_unit: Color,
+    pub conflict_cell: Color,
+    pub highlight_digit: Color,
+    pub highlight_note: Color,
+    pub highlight_block: Color,
+    pub victory_bkgrd: Color,
+    pub victory_border: Color,
+    pub victory_text: Color,
+    pub celebration: Color,
+    pub status_text: Color,
 }
 
 #[allow(dead_code)]
@@ -41,27 +55,22 @@ impl Colors {
 
     pub fn new (theme_name: String) -> Colors {
         let theme_id = Colors::get_theme_by_string(theme_name);
-        let result = Colors::fetch_colors(theme_id);
-        result
+        Colors::fetch_colors(theme_id)
     }
 
     fn get_theme_by_string(theme_name: String) -> ColorTheme {
-        let result: ColorTheme;
         match theme_name.trim().to_uppercase().as_str() {
-            "DARK" => result = ColorTheme::Dark,
-            _ => result = ColorTheme::Light,
+            "DARK" => ColorTheme::Dark,
+            _ => ColorTheme::Light,
         }
-        result
     }
 
     fn get_theme_name(theme_id: ColorTheme) -> String {
-        let result: String;
         match theme_id {
-            ColorTheme::Dark => result = "Dark".to_string(),
-            ColorTheme::Light => result = "Light".to_string(),
-            _ => result = "Unknown".to_string(),
+            ColorTheme::Dark => "Dark".to_string(),
+            ColorTheme::Light => "Light".to_string(),
+            _ => "Unknown".to_string(),
         }
-        result
     }
 
     fn fetch_colors(theme_id: ColorTheme) -> Colors {
@@ -95,6 +104,20 @@ impl Colors {
                 cols.number_default = Color::new(0.1, 0.1, 0.1, 1.0 );
                 cols.number_editable = Color::new(0.0, 0.0, 0.3, 1.0 );
                 cols.number_notvalid = Color::new(0.5, 0.0, 0.0, 1.0 );
+                cols.number_note = Color::new(0.3, 0.3, 0.5, 1.0 );
+                cols.hint_cell = Color::new(0.2, 0.5, 0.9, 0.3 );
+                cols.hint_target = Color::new(0.9, 0.7, 0.0, 0.6 );
+                cols.hint_candidate = Color::new(0.7, 0.0, 0.0, 1.0 );
+                cols.conflict_unit = Color::new(0.9, 0.3, 0.3, 0.15 );
+                cols.conflict_cell = Color::new(0.9, 0.2, 0.2, 0.45 );
+                cols.highlight_digit = Color::new(0.2, 0.5, 0.9, 0.35 );
+                cols.highlight_note = Color::new(0.2, 0.5, 0.9, 0.5 );
+                cols.highlight_block = Color::new(0.5, 0.5, 0.5, 0.15 );
+                cols.victory_bkgrd = Color::new(0.95, 0.95, 0.85, 0.95 );
+                cols.victory_border = Color::new(0.0, 0.6, 0.0, 1.0 );
+                cols.victory_text = Color::new(0.1, 0.1, 0.1, 1.0 );
+                cols.celebration = Color::new(0.0, 0.8, 0.2, 0.6 );
+                cols.status_text = Color::new(0.1, 0.1, 0.1, 1.0 );
             }
 
             ColorTheme::Dark => {
@@ -122,6 +145,20 @@ impl Colors {
                 cols.number_default = Color::new(0.4, 0.4, 0.4, 1.0 );
                 cols.number_editable = Color::new(0.0, 0.0, 0.5, 1.0 );
                 cols.number_notvalid = Color::new(0.5, 0.0, 0.0, 1.0 );
+                cols.number_note = Color::new(0.6, 0.6, 0.7, 1.0 );
+                cols.hint_cell = Color::new(0.1, 0.3, 0.6, 0.4 );
+                cols.hint_target = Color::new(0.5, 0.4, 0.0, 0.6 );
+                cols.hint_candidate = Color::new(0.8, 0.2, 0.2, 1.0 );
+                cols.conflict_unit = Color::new(0.6, 0.1, 0.1, 0.25 );
+                cols.conflict_cell = Color::new(0.7, 0.1, 0.1, 0.55 );
+                cols.highlight_digit = Color::new(0.1, 0.3, 0.6, 0.45 );
+                cols.highlight_note = Color::new(0.1, 0.3, 0.6, 0.6 );
+                cols.highlight_block = Color::new(0.6, 0.6, 0.6, 0.15 );
+                cols.victory_bkgrd = Color::new(0.2, 0.2, 0.2, 0.95 );
+                cols.victory_border = Color::new(0.0, 0.4, 0.0, 1.0 );
+                cols.victory_text = Color::new(0.7, 0.7, 0.7, 1.0 );
+                cols.celebration = Color::new(0.0, 0.5, 0.1, 0.6 );
+                cols.status_text = Color::new(0.7, 0.7, 0.7, 1.0 );
             }
 
             _ => ()
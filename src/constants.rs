@@ -1,10 +1,18 @@
+// the board dimensions and the generator settings are part of the engine
+pub use sudoku_engine::constants::*;
+
 pub const GAME_TITLE: &str = "Sudoku";
 
-// dimensions of the game
+// dimensions of the game, the buttons and the status bar are measured in cells
 pub const CELL_SIZE: i32 = 80;
-pub const BOARD_SIZE: i32 = 9;
-pub const FIELD_SIZE: i32 = 3;
-pub const GUI_SIZE: i32 = CELL_SIZE * 4;
+pub const MIN_CELL_SIZE: f32 = 40.0;
+pub const GUI_CELLS: f32 = 4.0;
+pub const STATUS_CELLS: f32 = 0.5;
+
+// seconds a message stays on the screen
+pub const MESSAGE_TIME: f64 = 4.0;
+// seconds of the animation after a solved puzzle
+pub const CELEBRATION_TIME: f64 = 2.0;
 
 
 // config and save files
@@ -16,14 +24,18 @@ pub const CONFIG_FILE: &str = "game.conf";
 pub const DEFAULT_FILE: &str = "_last";
 pub const FILE_EXT: &str = "rsdk";
 pub const SAVE_PATH: &str = "rs_sudoku";
+pub const EXPORT_PATH: &str = "export";
+pub const DAILY_FILE: &str = "daily.txt";
+pub const STATS_FILE: &str = "stats.txt";
+// puzzle files in the save dir which can be imported
+pub const PUZZLE_EXT: [&str; 3] = ["txt", "sdk", "ss"];
+
+// the running game is saved after these seconds or moves, 0 turns it off
+pub const AUTOSAVE_SECONDS: u32 = 60;
+pub const AUTOSAVE_MOVES: u32 = 10;
 
-// the board string
-// we have only one string for one difficulty, but we can replace any char for a number =
-// 9 x 9 = 81 different boards, plus we modify these boards with 6 modifiers (flipping, turning)
-// 81 different boards x 6 modifiers = 486 boards for each difficulty
-pub const BOARD_EASY: &str = "fecdjbgjiijhejcjbaadbjijjcfjjdcbjijhjjjfedjabbjjjhgjjejajhgjbfdjhijdfjecdbjjjejij";
-pub const BOARD_MOD: &str = "jaejghjbjghjfjbjacjbjejaihjefdabjjjjbjjghidjjjjjjjjacbjjjhijjefigjjfebjjfjcjjdjgi";
-pub const BOARD_HARD: &str = "jfjhjgjjdbjjjfjjighijjadjjjdejjhjjbjgjijjcjejjjjdjfjhijjhecafjjjdbjjjecjjjafjjijj";
+// the mistake limits of the options, 0 plays without a limit
+pub const MISTAKE_LIMITS: [u32; 4] = [0, 3, 5, 10];
 
 // UI buttons
 pub const BTN_NEW_EASY: u32 = 1;
@@ -35,3 +47,26 @@ pub const BTN_RESTART: u32 = 6;
 pub const BTN_SOLVE: u32 = 7;
 pub const BTN_BACK: u32 = 8;
 pub const BTN_QUIT: u32 = 9;
+pub const BTN_HINT: u32 = 10;
+pub const BTN_NOTES: u32 = 11;
+pub const BTN_AUTO_NOTES: u32 = 12;
+pub const BTN_UNDO: u32 = 13;
+pub const BTN_REDO: u32 = 14;
+pub const BTN_LOAD_GAME: u32 = 15;
+pub const BTN_DELETE_SAVE: u32 = 16;
+pub const BTN_CONTINUE: u32 = 17;
+pub const BTN_EXPORT: u32 = 18;
+pub const BTN_PASTE: u32 = 19;
+// started with the keyboard only, Ctrl+C in the game
+pub const BTN_COPY: u32 = 20;
+pub const BTN_PLAY_SEED: u32 = 21;
+// started with the keyboard only, after a seed is typed
+pub const BTN_START_SEED: u32 = 22;
+pub const BTN_DAILY: u32 = 23;
+pub const BTN_STATS: u32 = 24;
+pub const BTN_OPTIONS: u32 = 25;
+pub const BTN_CHECK_MODE: u32 = 26;
+pub const BTN_MISTAKE_LIMIT: u32 = 27;
+pub const BTN_HIGHLIGHT_DIGITS: u32 = 28;
+pub const BTN_HIGHLIGHT_NOTES: u32 = 29;
+pub const BTN_HIGHLIGHT_BLOCK: u32 = 30;
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+use sudoku_engine::board::SudokuBoard;
+use sudoku_engine::logic::Difficulty;
+
+use crate::constants::*;
+use crate::preferences::get_home_dir;
+use crate::storage::{get_date_from_days, get_time_from_seconds, write_atomic};
+
+// everyone plays the daily puzzle with the same difficulty
+pub const DAILY_DIFFICULTY: Difficulty = Difficulty::Moderate;
+
+// the solve times of the daily puzzles, the seed of a day is its date like 20241231
+#[derive(Debug, Default)]
+pub struct Daily {
+    solved: BTreeMap<u64, u64>,
+}
+
+impl Daily {
+
+    pub fn load() -> Daily {
+
+        let mut daily = Daily::default();
+        let path_name = Path::new(&get_home_dir()).join(SAVE_PATH).join(DAILY_FILE);
+
+        let text = match fs::read_to_string(&path_name) {
+            Ok(text) => text,
+            // nothing solved yet
+            Err(_) => return daily,
+        };
+
+        for line in text.lines() {
+            if let Some((seed, secs)) = line.split_once('=')
+                && let (Ok(seed), Ok(secs)) = (seed.trim().parse::<u64>(), secs.trim().parse::<u64>()) {
+                daily.solved.insert(seed, secs);
+            }
+        }
+
+        daily
+
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let mut text = format!("# {} daily puzzles, the date and the seconds to solve it\n", GAME_TITLE);
+        for (seed, secs) in self.solved.iter() {
+            text.push_str(&format!("{}={}\n", seed, secs));
+        }
+        let path_name = Path::new(&get_home_dir()).join(SAVE_PATH).join(DAILY_FILE);
+        write_atomic(&path_name, &text).map_err(|err| format!("Can't save daily puzzles: {}", err))
+    }
+
+    pub fn solve_time(&self, day: u64) -> Option<u64> {
+        self.solved.get(&daily_seed(day)).copied()
+    }
+
+    pub fn record(&mut self, seed: u64, secs: u64) {
+        // the first solution of a day counts
+        self.solved.entry(seed).or_insert(secs);
+    }
+
+    pub fn streak(&self, today: u64) -> u32 {
+        // an unsolved puzzle of today doesn't break the streak yet
+        let mut day = if self.solve_time(today).is_some() { today } else { today.saturating_sub(1) };
+        let mut streak: u32 = 0;
+        while self.solve_time(day).is_some() {
+            streak += 1;
+            if day == 0 {
+                break;
+            }
+            day -= 1;
+        }
+        streak
+    }
+
+}
+
+pub fn today() -> u64 {
+    // the same day for everyone, no matter of the time zone
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() / 86_400
+}
+
+pub fn daily_seed(day: u64) -> u64 {
+    let (year, month, day) = get_date_from_days(day);
+    year as u64 * 10_000 + month as u64 * 100 + day as u64
+}
+
+pub fn daily_board_seed(board: &SudokuBoard) -> Option<u64> {
+    // only a board started as daily puzzle counts, a normal seed may look like a date too
+    if board.is_daily() {
+        board.get_seed()
+    } else {
+        None
+    }
+}
+
+pub fn daily_info(daily: &Daily) -> String {
+    let today = today();
+    let streak = match daily.streak(today) {
+        1 => "1 day".to_string(),
+        n => format!("{} days", n),
+    };
+    match daily.solve_time(today) {
+        Some(secs) => {
+            let (hour, min, sec) = get_time_from_seconds(secs);
+            format!("Daily puzzle solved in {}:{:02}:{:02}, streak {}", hour, min, sec, streak)
+        }
+        None => format!("Daily puzzle not solved yet, streak {}", streak),
+    }
+}
@@ -20,51 +20,107 @@
 //
 
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
-mod board;
 mod button;
+mod cli;
 mod colors;
 mod constants;
+mod daily;
 mod preferences;
 mod rendering;
+mod stats;
+mod storage;
 
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use macroquad::{
-    main,
+    Window,
     window::Conf,
     prelude::*,
 };
-use crate::board::*;
+use sudoku_engine::board::*;
+use sudoku_engine::formats::*;
+use sudoku_engine::logic::{self, *};
+use sudoku_engine::rng::Rng;
+use sudoku_engine::savegame::SaveError;
 use crate::button::*;
 use crate::constants::*;
+use crate::daily::*;
 use crate::preferences::*;
 use crate::rendering::*;
+use crate::stats::*;
+use crate::storage::*;
 
 #[derive(PartialOrd, PartialEq)]
 pub enum GameMode {
     MainMenu,
+    LoadMenu,
+    StatsMenu,
+    OptionsMenu,
     InGame,
     EndGame,
 }
 
-#[main(game_window)]
-async fn main() -> Result<(), i32> {
+fn main() {
 
-    // load config
+    // a command line starts the text mode without a window
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        attach_console();
+        std::process::exit(cli::run(&args));
+    }
+
+    // the config knows the size of the window
     let config = Preferences::load();
 
+    Window::from_config(game_window(config.cell_size), async move {
+        if let Err(err) = game(config).await {
+            eprintln!("Error: {}", err);
+        }
+    });
+
+}
+
+async fn game(mut config: Preferences) -> Result<(), i32> {
+
+    // seed the random generator for the puzzle creation
+    let sys_secs = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let mut rng = Rng::new(sys_secs);
+
     // init
     let (mut select_x,mut select_y): (i32,i32) = (-1,-1);
     let (mut sel_menu_x,mut sel_menu_y): (f32,f32) = (0.0,0.0);
     let (mut mouse_x, mut mouse_y): (f32,f32);
     let mut number_selection: bool = false;
+    let mut notes_mode: bool = false;
+    let (mut cursor_x, mut cursor_y): (i32,i32) = (4,4);
+    let mut keyboard_cursor: bool = false;
+    let (mut last_mouse_x, mut last_mouse_y): (f32,f32) = mouse_position();
     let mut mouse_left_click: bool;
     let mut mouse_right_click: bool;
 
     let mut start_time = SystemTime::now();
     let mut game_duration: u64 = 0;
 
-    let board_size = (BOARD_SIZE * CELL_SIZE) as f32;
-    let cell_size = CELL_SIZE as f32;
+    let mut message = String::new();
+    let mut message_time: f64 = -MESSAGE_TIME;
+    let mut hint: Option<Step> = None;
+    let mut puzzle_done: bool = false;
+    let mut autosave_time: f64 = 0.0;
+    let mut autosave_edits: u32 = 0;
+
+    let mut saves: Vec<SaveInfo> = Vec::new();
+    let (mut save_sel, mut save_first): (usize,usize) = (0,0);
+    let mut delete_confirm: bool = false;
+    let mut seed_input: Option<String> = None;
+    let mut daily = Daily::load();
+    let mut stats = Stats::load();
+    let mut summaries: Vec<Summary> = Vec::new();
+    let mut victory: Option<Victory> = None;
+
+    let mut cell_size = config.cell_size.max(MIN_CELL_SIZE).floor();
+    let mut board_size = cell_size * BOARD_SIZE as f32;
 
     // init my simple GUI
     let mut game_mode = GameMode::MainMenu;
@@ -88,22 +144,121 @@ async fn main() -> Result<(), i32> {
 
     // init the sudoku board arrays
     let mut board: SudokuBoard = SudokuBoard::default();
+    board.set_check_mode(config.check_mode);
 
     // start loop
     'game_loop: loop {
 
-        // catch keyboard  input
-        // if is_key_released(KeyCode::Escape) {
-        //     break 'game_loop;
-        // }
+        // the layout follows the size of the window
+        let new_cell_size = fit_cell_size(screen_width(), screen_height());
+        if new_cell_size != cell_size {
+            cell_size = new_cell_size;
+            board_size = cell_size * BOARD_SIZE as f32;
+            render.resize(board_size, cell_size);
+            relayout(&mut buttons, &game_mode, board_size, cell_size, &config, notes_mode);
+        }
+
+        // a seed code is only typed in the main menu
+        if game_mode != GameMode::MainMenu {
+            seed_input = None;
+        }
+
+        // catch keyboard  input, a seed code gets all keys while it's typed
+        let shortcut: u32 = if seed_input.is_some() {
+            seed_typing(&mut seed_input)
+        } else {
+            shortcut_button(&game_mode)
+        };
+
+        if game_mode == GameMode::InGame {
+
+            // move the cursor, this closes the selection window
+            let (dx, dy) = cursor_movement();
+            if dx != 0 || dy != 0 {
+                cursor_x = (cursor_x + dx).clamp(0, BOARD_SIZE - 1);
+                cursor_y = (cursor_y + dy).clamp(0, BOARD_SIZE - 1);
+                keyboard_cursor = true;
+                number_selection = false;
+            }
+
+            // the selected cell of the selection window or the cursor gets the number
+            let (key_x, key_y) = if number_selection { (select_x, select_y) } else { (cursor_x, cursor_y) };
+            let editable = board.is_editable(key_x as usize, key_y as usize);
+
+            if let Some(num) = pressed_number() {
+                if editable {
+                    board.begin_move();
+                    if num == 0 {
+                        board.clear_cell(key_x as usize, key_y as usize);
+                    } else if notes_mode {
+                        board.enter_note(key_x as usize, key_y as usize, num);
+                    } else {
+                        board.enter_number(key_x as usize, key_y as usize, num, config.auto_clean_notes);
+                        hint = None;
+                    }
+                    board.end_move();
+                }
+                if !notes_mode || num == 0 {
+                    number_selection = false;
+                }
+                keyboard_cursor = true;
+            }
+
+            // open the selection window for the cursor cell
+            if (is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Space)) && !number_selection && editable {
+                select_x = cursor_x;
+                select_y = cursor_y;
+                number_selection = true;
+                keyboard_cursor = true;
+            }
+
+            if is_key_pressed(KeyCode::Escape) {
+                number_selection = false;
+            }
+
+        }
 
         // get mouse coords and clicked buttons
         (mouse_x,mouse_y) = mouse_position();
+        if mouse_x != last_mouse_x || mouse_y != last_mouse_y {
+            keyboard_cursor = false;
+            (last_mouse_x, last_mouse_y) = (mouse_x, mouse_y);
+        }
         mouse_left_click = is_mouse_button_released(MouseButton::Left);
         mouse_right_click = is_mouse_button_released(MouseButton::Right);
 
+        // select a saved game with the keyboard, the mouse or the mouse wheel
+        if game_mode == GameMode::LoadMenu && !saves.is_empty() {
+            let (_, dy) = cursor_movement();
+            let wheel = mouse_wheel().1;
+            let mut sel = save_sel as i32 + dy;
+            if wheel > 0.0 { sel -= 1; }
+            if wheel < 0.0 { sel += 1; }
+            if mouse_left_click && let Some(i) = render.list_index(mouse_x, mouse_y, save_first) {
+                sel = i as i32;
+            }
+            let sel = sel.clamp(0, saves.len() as i32 - 1) as usize;
+            if sel != save_sel {
+                delete_confirm = false;
+            }
+            save_sel = sel;
+            // scroll the list to the selected game
+            let rows = render.list_rows();
+            if save_sel < save_first {
+                save_first = save_sel;
+            } else if save_sel >= save_first + rows {
+                save_first = save_sel + 1 - rows;
+            }
+        }
+
+        // a click on the solved board shows or hides the summary
+        if mouse_left_click && game_mode == GameMode::EndGame && mouse_x < board_size && mouse_y < board_size
+            && let Some(v) = &mut victory {
+            v.visible = !v.visible;
+        }
+
         // process left mouse click
-        if mouse_left_click {
+        if mouse_left_click && game_mode == GameMode::InGame {
 
             // number selection == a cell waas selected and now the player can choose a number for the cell
             if number_selection {
@@ -111,14 +266,22 @@ async fn main() -> Result<(), i32> {
                 // get the selected number in the small selection window
                 let sel_num = render.selection_get_number(mouse_x, mouse_y, sel_menu_x, sel_menu_y);
 
+                // in notes mode toggle the note and keep the selection window open
+                if notes_mode && sel_num >= 1 && sel_num <= BOARD_SIZE as u8 {
+                    board.begin_move();
+                    board.enter_note(select_x as usize, select_y as usize, sel_num);
+                    board.end_move();
+
                 // check for vallid number
-                if sel_num >= 1 && sel_num <= BOARD_SIZE as u8 {
-                    if board.is_valid_move(select_x as usize, select_y as usize, sel_num) {
-                        board.set_as_valid(select_x as usize, select_y as usize);
+                } else {
+                    if sel_num >= 1 && sel_num <= BOARD_SIZE as u8 {
+                        board.begin_move();
+                        board.enter_number(select_x as usize, select_y as usize, sel_num, config.auto_clean_notes);
+                        board.end_move();
+                        hint = None;
                     }
-                    board.set_field(select_x as usize, select_y as usize, sel_num);
+                    number_selection = false;
                 }
-                number_selection = false;
 
             // the player clicked in a cell, get the cell and show if its editable
             } else {
@@ -127,17 +290,19 @@ async fn main() -> Result<(), i32> {
                 select_y = (mouse_y / cell_size) as i32;
 
                 // check if the click was inside the board
-                if select_x >= 0 && select_x < BOARD_SIZE {
+                if (0..BOARD_SIZE).contains(&select_x) && (0..BOARD_SIZE).contains(&select_y) {
+
+                    // the keyboard continues at the clicked cell
+                    cursor_x = select_x;
+                    cursor_y = select_y;
 
                     // if the cell isn't editable, reset the selection
                     if !board.is_editable(select_x as usize, select_y as usize) {
                         select_x = -1;
                         select_y = -1;
 
-                    // else, reset the cell and activate the small selection window
+                    // else, activate the small selection window
                     } else {
-                        board.set_field(select_x as usize, select_y as usize, 0);
-                        board.set_as_invalid(select_x as usize, select_y as usize);
                         number_selection = true;
                     }
                 }
@@ -151,12 +316,96 @@ async fn main() -> Result<(), i32> {
             select_y = -1;
         }
 
+        // the completed puzzle stops the timer, needs no autosave anymore and counts for the statistics
+        if game_mode == GameMode::InGame && !puzzle_done && board.is_solved() {
+            puzzle_done = true;
+            remove_autosave();
+            hint = None;
+            number_selection = false;
+            game_duration = SystemTime::now().duration_since(start_time).unwrap().as_secs();
+            let best = stats.best_time(board.get_difficulty());
+            let result = record_game(&mut stats, &board, game_duration, true, false);
+            victory = Some(Victory { result, best, time: get_time(), visible: true });
+            message = "Puzzle completed!".to_string();
+            if let Some(seed) = daily_board_seed(&board) {
+                daily.record(seed, game_duration);
+                if let Err(err) = daily.save() {
+                    eprintln!("{}", err);
+                }
+                message = format!("Puzzle completed! {}", daily_info(&daily));
+            }
+            message_time = get_time();
+            game_mode = GameMode::EndGame;
+            gamemode_endgame(&mut buttons);
+        }
+
+        // too many mistakes lose the game, this needs a check mode to see them
+        if game_mode == GameMode::InGame && !puzzle_done && config.mistake_limit > 0
+            && board.get_check_mode() != CheckMode::Off && board.mistakes_made() >= config.mistake_limit {
+            puzzle_done = true;
+            remove_autosave();
+            hint = None;
+            number_selection = false;
+            game_duration = SystemTime::now().duration_since(start_time).unwrap().as_secs();
+            record_game(&mut stats, &board, game_duration, false, false);
+            message = format!("{} mistakes, the game is lost", board.mistakes_made());
+            message_time = get_time();
+            game_mode = GameMode::EndGame;
+            gamemode_endgame(&mut buttons);
+        }
+
+        // save the running game from time to time and after some moves
+        if game_mode == GameMode::InGame && !puzzle_done {
+            let time_due = config.autosave_seconds > 0 && get_time() - autosave_time >= config.autosave_seconds as f64;
+            let moves_due = config.autosave_moves > 0 && board.edit_count() - autosave_edits >= config.autosave_moves;
+            if time_due || moves_due {
+                game_duration = SystemTime::now().duration_since(start_time).unwrap().as_secs();
+                if let Err(err) = save_game(&board, game_duration, true) {
+                    eprintln!("{}", err);
+                }
+                autosave_time = get_time();
+                autosave_edits = board.edit_count();
+            }
+        }
+
         // draw sudoku board
         render.board();
 
+        // show why a number is wrong, unless the player doesn't want to see it
+        if (game_mode == GameMode::InGame || game_mode == GameMode::EndGame) && board.get_check_mode() != CheckMode::Off {
+            render.conflicts(&board);
+        }
+
+        // draw the cells of the current hint
+        if let Some(step) = &hint {
+            render.hint(step);
+        }
+
+        // celebrate the solved puzzle for a moment
+        if game_mode == GameMode::EndGame && let Some(v) = &victory && get_time() - v.time < CELEBRATION_TIME {
+            render.celebration(get_time() - v.time);
+        }
+
+        // highlight the numbers and the block of the cell under the cursor or of the selected cell
+        if game_mode == GameMode::InGame || game_mode == GameMode::EndGame {
+            let (focus_x, focus_y) = if number_selection {
+                (select_x, select_y)
+            } else if keyboard_cursor {
+                (cursor_x, cursor_y)
+            } else {
+                ((mouse_x / cell_size).floor() as i32, (mouse_y / cell_size).floor() as i32)
+            };
+            if (0..BOARD_SIZE).contains(&focus_x) && (0..BOARD_SIZE).contains(&focus_y) {
+                render.highlights(&board, focus_x as usize, focus_y as usize,
+                                  config.highlight_digits, config.highlight_notes, config.highlight_block);
+            }
+        }
+
         // draw cell marker
-        if !number_selection {
-            if game_mode != GameMode::MainMenu {
+        if !number_selection && game_mode != GameMode::MainMenu {
+            if keyboard_cursor {
+                render.cell_marker((cursor_x as f32 + 0.5) * cell_size, (cursor_y as f32 + 0.5) * cell_size);
+            } else {
                 render.cell_marker(mouse_x, mouse_y);
             }
         }
@@ -171,13 +420,40 @@ async fn main() -> Result<(), i32> {
 
         // draw the submenu to select a number
         if number_selection {
-            (sel_menu_x,sel_menu_y) = render.selection_board(select_x, select_y);
+            let marked = if notes_mode { board.get_notes(select_x as usize, select_y as usize) } else { 0 };
+            (sel_menu_x,sel_menu_y) = render.selection_board(select_x, select_y, marked);
             render.selection_marker(sel_menu_x, sel_menu_y, mouse_x, mouse_y);
         }
 
+        // draw the summary of the solved puzzle
+        if game_mode == GameMode::EndGame && let Some(v) = &victory && v.visible {
+            render.victory(v);
+        }
+
         // draw title in main menu
         if game_mode == GameMode::MainMenu {
             render.sudoku_title();
+        } else if game_mode == GameMode::LoadMenu {
+            let lines: Vec<String> = saves.iter().map(save_line).collect();
+            let title = if saves.is_empty() { "No saved games" } else { "Saved games" };
+            render.list(title, &lines, save_sel, save_first, mouse_x, mouse_y);
+        } else if game_mode == GameMode::StatsMenu {
+            render.statistics(&summaries);
+        } else if game_mode == GameMode::OptionsMenu {
+            render.text_page("Options", &[
+                "Check marks wrong numbers of the player:",
+                "  Off - no number is marked",
+                "  Conflicts - the same number in a row,",
+                "      column or block",
+                "  Solution - a number which differs from",
+                "      the solution of the puzzle",
+                "",
+                "Mistake limit loses the game after so many",
+                "wrong numbers, it doesn't work without a check.",
+                "",
+                "The cell under the cursor highlights the same",
+                "numbers, the notes with its number and its block.",
+            ]);
         } else {
             if game_mode == GameMode::InGame {
                 game_duration = SystemTime::now().duration_since(start_time).unwrap().as_secs();
@@ -185,56 +461,286 @@ async fn main() -> Result<(), i32> {
             render.game_timer(game_duration);
         }
 
+        // draw the hint or the last message for a few seconds in the status bar
+        if let Some(step) = &hint {
+            render.message(&step.description);
+        } else if let Some(code) = &seed_input {
+            render.message(&format!("Seed: {}_    Enter plays, Escape cancels", code));
+        } else if get_time() - message_time < MESSAGE_TIME {
+            render.message(&message);
+        } else if game_mode == GameMode::InGame || game_mode == GameMode::EndGame {
+            render.message(&board_info(&board));
+        } else if game_mode == GameMode::MainMenu {
+            render.message(&daily_info(&daily));
+        } else {
+            render.message("");
+        }
+
         // draw gui
         let sel_button: u32 = buttons.draw(mouse_x, mouse_y, &render);
 
-        // if mouse left button was clicked, check if a button was clicked
-        if mouse_left_click {
+        // a clicked button or a pressed shortcut starts an action
+        // copying the board and the start of a typed seed have no button, they come only from the keyboard
+        let mut action: u32 = if mouse_left_click { sel_button } else { 0 };
+        if action == 0 && (buttons.has_button(shortcut) || shortcut == BTN_START_SEED || shortcut == BTN_COPY) {
+            action = shortcut;
+        }
 
-            match sel_button {
+        if action != 0 {
 
-                BTN_NEW_EASY => {
-                    board.edit(&BOARD_EASY.to_string());
-                    game_mode = GameMode::InGame;
-                    start_time = SystemTime::now();
-                    gamemode_ingame(&mut buttons, board_size, cell_size);
+            match action {
+
+                BTN_NEW_EASY | BTN_NEW_MOD | BTN_NEW_HARD | BTN_DAILY | BTN_START_SEED => {
+                    let start = match action {
+                        BTN_NEW_EASY => Some((Difficulty::Easy, new_seed(&mut rng))),
+                        BTN_NEW_MOD => Some((Difficulty::Moderate, new_seed(&mut rng))),
+                        BTN_NEW_HARD => Some((Difficulty::Hard, new_seed(&mut rng))),
+                        BTN_DAILY => Some((DAILY_DIFFICULTY, daily_seed(today()))),
+                        _ => parse_seed_code(&seed_input.take().unwrap_or_default()),
+                    };
+                    if let Some((difficulty, seed)) = start {
+                        let grade = generate(&mut board, difficulty, seed);
+                        board.set_daily(action == BTN_DAILY);
+                        message = match check_puzzle(&board) {
+                            Some(warning) => warning,
+                            None => format!("{} board, seed {}, score {} ({})", difficulty.name(), seed_code(difficulty, seed),
+                                            grade.score, grade.hardest.map_or("no technique", |t| t.name())),
+                        };
+                        puzzle_done = false;
+                        game_mode = GameMode::InGame;
+                        start_time = SystemTime::now();
+                        (autosave_time, autosave_edits) = (get_time(), board.edit_count());
+                        gamemode_ingame(&mut buttons, board_size, cell_size);
+                        buttons.set_text(BTN_NOTES, notes_label(notes_mode));
+                    } else {
+                        message = "Unknown seed, it needs the letter of the difficulty and a number, like M-12345".to_string();
+                    }
+                    message_time = get_time();
                 }
 
-                BTN_NEW_MOD => {
-                    board.edit(&BOARD_MOD.to_string());
-                    game_mode = GameMode::InGame;
-                    start_time = SystemTime::now();
-                    gamemode_ingame(&mut buttons, board_size, cell_size);
+                BTN_PLAY_SEED => {
+                    // forget all keys typed before
+                    clear_input_queue();
+                    seed_input = Some(String::new());
                 }
 
-                BTN_NEW_HARD => {
-                    board.edit(&BOARD_HARD.to_string());
-                    game_mode = GameMode::InGame;
-                    start_time = SystemTime::now();
-                    gamemode_ingame(&mut buttons, board_size, cell_size);
+                BTN_LOAD => {
+                    saves = list_saves();
+                    (save_sel, save_first) = (0, 0);
+                    delete_confirm = false;
+                    game_mode = GameMode::LoadMenu;
+                    gamemode_loadmenu(&mut buttons, board_size, cell_size);
+                }
+
+                BTN_STATS => {
+                    summaries = stats.summaries();
+                    game_mode = GameMode::StatsMenu;
+                    gamemode_statsmenu(&mut buttons, board_size, cell_size);
+                }
+
+                BTN_OPTIONS => {
+                    game_mode = GameMode::OptionsMenu;
+                    gamemode_optionsmenu(&mut buttons, board_size, cell_size, &config);
+                }
+
+                BTN_CHECK_MODE | BTN_MISTAKE_LIMIT | BTN_HIGHLIGHT_DIGITS | BTN_HIGHLIGHT_NOTES | BTN_HIGHLIGHT_BLOCK => {
+                    match action {
+                        BTN_CHECK_MODE => {
+                            config.check_mode = config.check_mode.next();
+                            board.set_check_mode(config.check_mode);
+                        }
+                        BTN_MISTAKE_LIMIT => {
+                            let i = MISTAKE_LIMITS.iter().position(|l| *l == config.mistake_limit).map_or(0, |i| i + 1);
+                            config.mistake_limit = MISTAKE_LIMITS[i % MISTAKE_LIMITS.len()];
+                        }
+                        BTN_HIGHLIGHT_DIGITS => config.highlight_digits = !config.highlight_digits,
+                        BTN_HIGHLIGHT_NOTES => config.highlight_notes = !config.highlight_notes,
+                        _ => config.highlight_block = !config.highlight_block,
+                    }
+                    gamemode_optionsmenu(&mut buttons, board_size, cell_size, &config);
+                    if let Err(err) = config.save() {
+                        eprintln!("{}", err);
+                    }
                 }
 
-                BTN_LOAD => (), // TODO
+                BTN_PASTE | BTN_LOAD_GAME if action == BTN_PASTE || saves.get(save_sel).is_some_and(|info| info.puzzle.is_some()) => {
+                    let (result, source) = if action == BTN_PASTE {
+                        (paste_puzzle(&mut board), "the clipboard".to_string())
+                    } else {
+                        let info = &saves[save_sel];
+                        let index = info.puzzle.unwrap_or_default();
+                        (import_puzzle(&mut board, &info.file_name, index), format!("puzzle {} of {}", index + 1, info.file_name))
+                    };
+                    match result {
+                        Ok(_) => {
+                            let grade = grade(&board);
+                            let difficulty = Difficulty::from_score(grade.score);
+                            board.set_difficulty(Some(difficulty));
+                            message = format!("Puzzle from {}, {} (score {})", source, difficulty.name(), grade.score);
+                            puzzle_done = false;
+                            hint = None;
+                            number_selection = false;
+                            game_mode = GameMode::InGame;
+                            start_time = SystemTime::now();
+                            (autosave_time, autosave_edits) = (get_time(), board.edit_count());
+                            gamemode_ingame(&mut buttons, board_size, cell_size);
+                            buttons.set_text(BTN_NOTES, notes_label(notes_mode));
+                        }
+                        Err(err) => message = format!("Can't import {}: {}", source, err),
+                    }
+                    message_time = get_time();
+                }
+
+                BTN_COPY => {
+                    miniquad::window::clipboard_set(write_puzzle(&board.grid(), Format::Line).trim_end());
+                    message = "Board copied to the clipboard".to_string();
+                    message_time = get_time();
+                }
+
+                BTN_LOAD_GAME | BTN_CONTINUE => {
+                    let file_name = if action == BTN_CONTINUE {
+                        Some(autosave_name())
+                    } else {
+                        saves.get(save_sel).map(|info| info.file_name.clone())
+                    };
+                    if let Some(file_name) = file_name {
+                        // the game continues with the time it was saved with
+                        let loaded = load_game(&mut board, file_name.clone()).and_then(|duration| {
+                            SystemTime::now()
+                                .checked_sub(Duration::from_secs(duration))
+                                .ok_or(SaveError::InvalidField("duration"))
+                        });
+                        match loaded {
+                            Ok(time) => {
+                                message = match check_puzzle(&board) {
+                                    Some(warning) => warning,
+                                    None => format!("Loaded {}", file_name),
+                                };
+                                puzzle_done = board.is_solved();
+                                hint = None;
+                                number_selection = false;
+                                game_mode = GameMode::InGame;
+                                start_time = time;
+                                (autosave_time, autosave_edits) = (get_time(), board.edit_count());
+                                gamemode_ingame(&mut buttons, board_size, cell_size);
+                                buttons.set_text(BTN_NOTES, notes_label(notes_mode));
+                            }
+                            Err(err) => message = format!("Can't load {}: {}", file_name, err),
+                        }
+                        message_time = get_time();
+                    }
+                }
+
+                BTN_DELETE_SAVE => {
+                    if let Some(info) = saves.get(save_sel) {
+                        // delete only after a second click, puzzle files stay untouched
+                        if info.puzzle.is_some() {
+                            message = format!("{} is a puzzle file and can't be deleted here", info.file_name);
+                        } else if !delete_confirm {
+                            message = format!("Click again to delete {}", info.file_name);
+                            delete_confirm = true;
+                        } else {
+                            message = match delete_save(&info.file_name) {
+                                Ok(_) => format!("Deleted {}", info.file_name),
+                                Err(err) => err,
+                            };
+                            saves = list_saves();
+                            save_sel = save_sel.min(saves.len().saturating_sub(1));
+                            save_first = save_first.min(save_sel);
+                            delete_confirm = false;
+                        }
+                        message_time = get_time();
+                    }
+                }
 
                 BTN_SAVE => {
                     game_duration = SystemTime::now().duration_since(start_time).unwrap().as_secs();
-                    board.save(game_duration, false)
+                    message = match save_game(&board, game_duration, false) {
+                        Ok(_) => "Game saved".to_string(),
+                        Err(err) => err,
+                    };
+                    message_time = get_time();
+                },
+
+                BTN_EXPORT => {
+                    message = match export_board(&board) {
+                        Ok(path_name) => format!("Exported to {}", path_name),
+                        Err(err) => err,
+                    };
+                    message_time = get_time();
                 },
 
                 BTN_RESTART => {
                     start_time = SystemTime::now();
-                    board.reset()
+                    hint = None;
+                    board.begin_move();
+                    board.reset();
+                    board.end_move();
                 },
 
+                BTN_UNDO => {
+                    board.undo();
+                    hint = None;
+                }
+
+                BTN_REDO => {
+                    board.redo();
+                    hint = None;
+                }
+
+                BTN_HINT => {
+                    match logic::hint(&board) {
+                        Ok(step) => {
+                            board.add_hint();
+                            hint = Some(step);
+                        }
+                        Err(err) => {
+                            hint = None;
+                            message = err;
+                            message_time = get_time();
+                        }
+                    }
+                }
+
+                BTN_NOTES => {
+                    notes_mode = !notes_mode;
+                    buttons.set_text(BTN_NOTES, notes_label(notes_mode));
+                }
+
+                BTN_AUTO_NOTES => {
+                    board.begin_move();
+                    board.fill_candidates();
+                    board.end_move();
+                }
+
                 BTN_SOLVE => {
-                    _ = board.solve();
-                    game_mode = GameMode::EndGame;
+                    hint = None;
                     game_duration = SystemTime::now().duration_since(start_time).unwrap().as_secs();
-                    buttons.del_button(BTN_SOLVE);
-                    buttons.del_button(BTN_RESTART);
+                    if board.fill_solution() {
+                        // a game given up with the solver counts as not completed
+                        if !puzzle_done {
+                            record_game(&mut stats, &board, game_duration, false, true);
+                        }
+                        puzzle_done = true;
+                        remove_autosave();
+                        game_mode = GameMode::EndGame;
+                        gamemode_endgame(&mut buttons);
+                    } else {
+                        message = "This puzzle has no solution".to_string();
+                        message_time = get_time();
+                    }
                 }
 
                 BTN_BACK => {
+                    // keep the unfinished game to continue it later
+                    if game_mode == GameMode::InGame && !puzzle_done {
+                        game_duration = SystemTime::now().duration_since(start_time).unwrap().as_secs();
+                        if let Err(err) = save_game(&board, game_duration, true) {
+                            eprintln!("{}", err);
+                        }
+                    }
+                    hint = None;
+                    victory = None;
                     board.clear();
                     game_mode = GameMode::MainMenu;
                     game_duration = SystemTime::now().duration_since(start_time).unwrap().as_secs();
@@ -253,11 +759,15 @@ async fn main() -> Result<(), i32> {
 
     }
 
-    if game_mode == GameMode::InGame {
+    if game_mode == GameMode::InGame && !puzzle_done {
         game_duration = SystemTime::now().duration_since(start_time).unwrap().as_secs();
-        board.save(game_duration, true);
+        if let Err(err) = save_game(&board, game_duration, true) {
+            eprintln!("{}", err);
+        }
     }
 
+    // the next start uses the last size of the window
+    config.cell_size = cell_size;
     match config.save() {
         Ok(_) => (),
         Err(err) => {
@@ -270,38 +780,321 @@ async fn main() -> Result<(), i32> {
 
 }
 
-fn game_window() -> Conf {
+// a windows release build has no console of its own, the text goes to the console of the shell
+#[cfg(windows)]
+fn attach_console() {
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    // without a parent console, like a start from the explorer, nothing is printed
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_console() {}
+
+fn game_window(cell_size: f32) -> Conf {
+    let cell_size = cell_size.max(MIN_CELL_SIZE);
     Conf {
         window_title: GAME_TITLE.to_string(),
-        window_width: BOARD_SIZE * CELL_SIZE + GUI_SIZE,
-        window_height: BOARD_SIZE * CELL_SIZE,
-        window_resizable: false,
+        window_width: (cell_size * (BOARD_SIZE as f32 + GUI_CELLS)) as i32,
+        window_height: (cell_size * (BOARD_SIZE as f32 + STATUS_CELLS)) as i32,
+        window_resizable: true,
+        high_dpi: true,
         fullscreen: false,
         sample_count: 4,
         ..Default::default()
     }
 }
 
+fn fit_cell_size(width: f32, height: f32) -> f32 {
+    // whole pixels keep the lines of the board sharp
+    let cell_size = (width / (BOARD_SIZE as f32 + GUI_CELLS)).min(height / (BOARD_SIZE as f32 + STATUS_CELLS));
+    cell_size.floor().max(MIN_CELL_SIZE)
+}
+
+fn relayout(btns: &mut Buttons, game_mode: &GameMode, board_size: f32, cell_size: f32, config: &Preferences, notes_mode: bool) {
+    match game_mode {
+        GameMode::MainMenu => gamemode_mainmenu(btns, board_size, cell_size),
+        GameMode::LoadMenu => gamemode_loadmenu(btns, board_size, cell_size),
+        GameMode::StatsMenu => gamemode_statsmenu(btns, board_size, cell_size),
+        GameMode::OptionsMenu => gamemode_optionsmenu(btns, board_size, cell_size, config),
+        GameMode::InGame | GameMode::EndGame => {
+            gamemode_ingame(btns, board_size, cell_size);
+            btns.set_text(BTN_NOTES, notes_label(notes_mode));
+            if *game_mode == GameMode::EndGame {
+                gamemode_endgame(btns);
+            }
+        }
+    }
+}
+
+fn remove_autosave() {
+    if autosave_exists() && let Err(err) = delete_save(&autosave_name()) {
+        eprintln!("{}", err);
+    }
+}
+
+fn record_game(stats: &mut Stats, board: &SudokuBoard, duration: u64, solved: bool, solver: bool) -> GameResult {
+    let result = GameResult::new(board.get_difficulty(), duration, board.hints_used(), board.mistakes_made(), solved, solver);
+    stats.record(result.clone());
+    if let Err(err) = stats.save() {
+        eprintln!("{}", err);
+    }
+    result
+}
+
+fn paste_puzzle(board: &mut SudokuBoard) -> Result<(), String> {
+    let text = miniquad::window::clipboard_get().unwrap_or_default();
+    if text.trim().is_empty() {
+        return Err("the clipboard is empty".to_string());
+    }
+    match parse_puzzles(&text) {
+        Ok(puzzles) if puzzles.len() == 1 => board.set_puzzle(&puzzles[0]),
+        Ok(_) => Err("the clipboard has more than one puzzle".to_string()),
+        Err(err) => Err(format!("no valid puzzle, {}", err)),
+    }
+}
+
+fn check_puzzle(board: &SudokuBoard) -> Option<String> {
+    match board.givens().count_solutions(2) {
+        0 => Some("Warning: this puzzle has no solution".to_string()),
+        1 => None,
+        _ => Some("Warning: this puzzle has more than one solution".to_string()),
+    }
+}
+
+fn save_line(info: &SaveInfo) -> String {
+    let (year, month, day) = get_date_from_days(info.modified / 86_400);
+    let (hour, min, _) = get_time_from_seconds(info.modified % 86_400);
+    if let Some(index) = info.puzzle {
+        return format!("{}-{:02}-{:02} {:02}:{:02}  puzzle {} of {}  {:3}% given",
+                       year, month, day, hour, min, index + 1, info.file_name, info.progress);
+    }
+    let (d_hour, d_min, d_sec) = get_time_from_seconds(info.duration);
+    let difficulty = info.difficulty.map_or("", |d| d.name());
+    format!("{}-{:02}-{:02} {:02}:{:02}  {:8}  {}:{:02}:{:02}  {:3}%  {} hints",
+            year, month, day, hour, min, difficulty, d_hour, d_min, d_sec, info.progress, info.hints)
+}
+
+fn shortcut_button(game_mode: &GameMode) -> u32 {
+    let ctrl = is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl);
+    let shift = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+    if *game_mode == GameMode::MainMenu && ctrl {
+        if is_key_pressed(KeyCode::V) { return BTN_PASTE; }
+    } else if *game_mode == GameMode::MainMenu {
+        if is_key_pressed(KeyCode::Key1) { return BTN_NEW_EASY; }
+        if is_key_pressed(KeyCode::Key2) { return BTN_NEW_MOD; }
+        if is_key_pressed(KeyCode::Key3) { return BTN_NEW_HARD; }
+        if is_key_pressed(KeyCode::C) { return BTN_CONTINUE; }
+        if is_key_pressed(KeyCode::L) { return BTN_LOAD; }
+        if is_key_pressed(KeyCode::P) { return BTN_PLAY_SEED; }
+        if is_key_pressed(KeyCode::D) { return BTN_DAILY; }
+        if is_key_pressed(KeyCode::S) { return BTN_STATS; }
+        if is_key_pressed(KeyCode::O) { return BTN_OPTIONS; }
+        if is_key_pressed(KeyCode::Q) || is_key_pressed(KeyCode::Escape) { return BTN_QUIT; }
+    } else if *game_mode == GameMode::LoadMenu {
+        if is_key_pressed(KeyCode::Enter) { return BTN_LOAD_GAME; }
+        if is_key_pressed(KeyCode::Delete) { return BTN_DELETE_SAVE; }
+        if is_key_pressed(KeyCode::Escape) { return BTN_BACK; }
+    } else if *game_mode == GameMode::StatsMenu {
+        if is_key_pressed(KeyCode::Escape) { return BTN_BACK; }
+    } else if *game_mode == GameMode::OptionsMenu {
+        if is_key_pressed(KeyCode::C) { return BTN_CHECK_MODE; }
+        if is_key_pressed(KeyCode::M) { return BTN_MISTAKE_LIMIT; }
+        if is_key_pressed(KeyCode::D) { return BTN_HIGHLIGHT_DIGITS; }
+        if is_key_pressed(KeyCode::N) { return BTN_HIGHLIGHT_NOTES; }
+        if is_key_pressed(KeyCode::B) { return BTN_HIGHLIGHT_BLOCK; }
+        if is_key_pressed(KeyCode::Escape) { return BTN_BACK; }
+    } else if ctrl {
+        if is_key_pressed(KeyCode::S) { return BTN_SAVE; }
+        if is_key_pressed(KeyCode::E) { return BTN_EXPORT; }
+        if is_key_pressed(KeyCode::C) { return BTN_COPY; }
+        if is_key_pressed(KeyCode::Y) || (shift && is_key_pressed(KeyCode::Z)) { return BTN_REDO; }
+        if is_key_pressed(KeyCode::Z) { return BTN_UNDO; }
+        if is_key_pressed(KeyCode::Q) { return BTN_QUIT; }
+    } else {
+        if is_key_pressed(KeyCode::F1) { return BTN_HINT; }
+        if is_key_pressed(KeyCode::N) || is_key_pressed(KeyCode::F2) { return BTN_NOTES; }
+        if is_key_pressed(KeyCode::F3) { return BTN_AUTO_NOTES; }
+        if is_key_pressed(KeyCode::F5) { return BTN_RESTART; }
+        if is_key_pressed(KeyCode::F8) { return BTN_SOLVE; }
+        if is_key_pressed(KeyCode::F10) { return BTN_BACK; }
+    }
+    0
+}
+
+fn seed_typing(input: &mut Option<String>) -> u32 {
+    let Some(code) = input else {
+        return 0;
+    };
+    while let Some(c) = get_char_pressed() {
+        if (c.is_ascii_alphanumeric() || c == '-') && code.len() < 16 {
+            code.push(c.to_ascii_uppercase());
+        }
+    }
+    if is_key_pressed(KeyCode::Backspace) {
+        code.pop();
+    }
+    if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::KpEnter) {
+        return BTN_START_SEED;
+    }
+    if is_key_pressed(KeyCode::Escape) {
+        *input = None;
+    }
+    0
+}
+
+fn board_info(board: &SudokuBoard) -> String {
+    if let Some(seed) = daily_board_seed(board) {
+        return format!("Daily puzzle of {}-{:02}-{:02}", seed / 10_000, seed / 100 % 100, seed % 100);
+    }
+    match (board.get_difficulty(), board.get_seed()) {
+        (Some(difficulty), Some(seed)) => format!("{} board, seed {}", difficulty.name(), seed_code(difficulty, seed)),
+        (Some(difficulty), None) => format!("{} board", difficulty.name()),
+        _ => String::new(),
+    }
+}
+
+fn cursor_movement() -> (i32,i32) {
+    let ctrl = is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl);
+    if ctrl {
+        return (0,0);
+    }
+    let pressed = |keys: [KeyCode; 3]| keys.iter().any(|&k| is_key_pressed(k));
+    let mut result: (i32,i32) = (0,0);
+    if pressed([KeyCode::Left, KeyCode::A, KeyCode::H]) { result.0 -= 1; }
+    if pressed([KeyCode::Right, KeyCode::D, KeyCode::L]) { result.0 += 1; }
+    if pressed([KeyCode::Up, KeyCode::W, KeyCode::K]) { result.1 -= 1; }
+    if pressed([KeyCode::Down, KeyCode::S, KeyCode::J]) { result.1 += 1; }
+    result
+}
+
+fn pressed_number() -> Option<u8> {
+    let keys = [
+        (KeyCode::Key1, KeyCode::Kp1), (KeyCode::Key2, KeyCode::Kp2), (KeyCode::Key3, KeyCode::Kp3),
+        (KeyCode::Key4, KeyCode::Kp4), (KeyCode::Key5, KeyCode::Kp5), (KeyCode::Key6, KeyCode::Kp6),
+        (KeyCode::Key7, KeyCode::Kp7), (KeyCode::Key8, KeyCode::Kp8), (KeyCode::Key9, KeyCode::Kp9),
+    ];
+    for (i, &(key, kp)) in keys.iter().enumerate() {
+        if is_key_pressed(key) || is_key_pressed(kp) {
+            return Some(i as u8 + 1);
+        }
+    }
+    if [KeyCode::Key0, KeyCode::Kp0, KeyCode::Backspace, KeyCode::Delete].iter().any(|&k| is_key_pressed(k)) {
+        return Some(0);
+    }
+    None
+}
+
+fn notes_label(notes_mode: bool) -> &'static str {
+    if notes_mode { "Notes: on" } else { "Notes: off" }
+}
+
+fn check_label(check_mode: CheckMode) -> String {
+    format!("Check: {}", check_mode.name().to_lowercase())
+}
+
+fn limit_label(mistake_limit: u32) -> String {
+    match mistake_limit {
+        0 => "Mistake limit: off".to_string(),
+        n => format!("Mistake limit: {}", n),
+    }
+}
+
+fn switch_label(name: &str, on: bool) -> String {
+    format!("{}: {}", name, if on { "on" } else { "off" })
+}
+
+fn button_row(cell_size: f32, row: f32) -> f32 {
+    cell_size * (0.25 + row * 0.85)
+}
+
 fn gamemode_mainmenu(btns: &mut Buttons, board_size: f32, cell_size: f32) {
     let x = board_size + cell_size * 0.5;
     let bw = cell_size * 3.0;
-    let bh = cell_size * 0.75;
+    let bh = cell_size * 0.65;
+    btns.reset();
+    if autosave_exists() {
+        btns.new_button(BTN_CONTINUE, x, button_row(cell_size, 0.0), bw, bh, "Continue last game");
+    }
+    btns.new_button(BTN_NEW_EASY, x, button_row(cell_size, 1.0), bw, bh, "New easy board");
+    btns.new_button(BTN_NEW_MOD, x, button_row(cell_size, 2.0), bw, bh, "New moderate board");
+    btns.new_button(BTN_NEW_HARD, x, button_row(cell_size, 3.0), bw, bh, "New hard board");
+    btns.new_button(BTN_LOAD, x, button_row(cell_size, 4.0), bw, bh, "Load board");
+    btns.new_button(BTN_PASTE, x, button_row(cell_size, 5.0), bw, bh, "Paste puzzle");
+    btns.new_button(BTN_PLAY_SEED, x, button_row(cell_size, 6.0), bw, bh, "Play seed...");
+    btns.new_button(BTN_DAILY, x, button_row(cell_size, 7.0), bw, bh, "Daily puzzle");
+    btns.new_button(BTN_STATS, x, button_row(cell_size, 8.0), (bw - bh) * 0.5, bh, "Stats");
+    btns.new_button(BTN_OPTIONS, x + (bw + bh) * 0.5, button_row(cell_size, 8.0), (bw - bh) * 0.5, bh, "Options");
+    btns.new_button(BTN_QUIT, x, button_row(cell_size, 9.0), bw, bh, "Quit");
+}
+
+fn gamemode_loadmenu(btns: &mut Buttons, board_size: f32, cell_size: f32) {
+    let x = board_size + cell_size * 0.5;
+    let bw = cell_size * 3.0;
+    let bh = cell_size * 0.65;
+    btns.reset();
+    btns.new_button(BTN_LOAD_GAME, x, button_row(cell_size, 0.0), bw, bh, "Load game");
+    btns.new_button(BTN_DELETE_SAVE, x, button_row(cell_size, 1.0), bw, bh, "Delete save");
+    btns.new_button(BTN_BACK, x, button_row(cell_size, 8.0), bw, bh, "Back to Main");
+    btns.new_button(BTN_QUIT, x, button_row(cell_size, 9.0), bw, bh, "Quit");
+}
+
+fn gamemode_endgame(btns: &mut Buttons) {
+    // a finished board can't be played or saved anymore
+    btns.del_button(BTN_SAVE);
+    btns.del_button(BTN_SOLVE);
+    btns.del_button(BTN_RESTART);
+    btns.del_button(BTN_HINT);
+    btns.del_button(BTN_NOTES);
+    btns.del_button(BTN_AUTO_NOTES);
+    btns.del_button(BTN_UNDO);
+    btns.del_button(BTN_REDO);
+}
+
+fn gamemode_statsmenu(btns: &mut Buttons, board_size: f32, cell_size: f32) {
+    let x = board_size + cell_size * 0.5;
+    let bw = cell_size * 3.0;
+    let bh = cell_size * 0.65;
+    btns.reset();
+    btns.new_button(BTN_BACK, x, button_row(cell_size, 8.0), bw, bh, "Back to Main");
+    btns.new_button(BTN_QUIT, x, button_row(cell_size, 9.0), bw, bh, "Quit");
+}
+
+fn gamemode_optionsmenu(btns: &mut Buttons, board_size: f32, cell_size: f32, config: &Preferences) {
+    let x = board_size + cell_size * 0.5;
+    let bw = cell_size * 3.0;
+    let bh = cell_size * 0.65;
     btns.reset();
-    btns.new_button(BTN_NEW_EASY,x, cell_size * 0.5, bw, bh, "New easy board");
-    btns.new_button(BTN_NEW_MOD,x, cell_size * 2.0, bw, bh, "New moderate board");
-    btns.new_button(BTN_NEW_HARD,x, cell_size * 3.5, bw, bh, "New hard board");
-    //btns.new_button(BTN_LOAD,x, cell_size * 5.5, bw, bh, "Load board");
-    btns.new_button(BTN_QUIT,x, cell_size * 7.5, bw, bh, "Quit");
+    btns.new_button(BTN_CHECK_MODE, x, button_row(cell_size, 0.0), bw, bh, &check_label(config.check_mode));
+    btns.new_button(BTN_MISTAKE_LIMIT, x, button_row(cell_size, 1.0), bw, bh, &limit_label(config.mistake_limit));
+    btns.new_button(BTN_HIGHLIGHT_DIGITS, x, button_row(cell_size, 2.0), bw, bh, &switch_label("Same numbers", config.highlight_digits));
+    btns.new_button(BTN_HIGHLIGHT_NOTES, x, button_row(cell_size, 3.0), bw, bh, &switch_label("Same notes", config.highlight_notes));
+    btns.new_button(BTN_HIGHLIGHT_BLOCK, x, button_row(cell_size, 4.0), bw, bh, &switch_label("Block", config.highlight_block));
+    btns.new_button(BTN_BACK, x, button_row(cell_size, 8.0), bw, bh, "Back to Main");
+    btns.new_button(BTN_QUIT, x, button_row(cell_size, 9.0), bw, bh, "Quit");
 }
 
 fn gamemode_ingame(btns: &mut Buttons, board_size: f32, cell_size: f32) {
     let x = board_size + cell_size * 0.5;
     let bw = cell_size * 3.0;
-    let bh = cell_size * 0.75;
+    let bh = cell_size * 0.65;
+    let row = |i: f32| button_row(cell_size, i + 1.0);
     btns.reset();
-    btns.new_button(BTN_SOLVE, x, cell_size * 0.5, bw, bh, "Solve board");
-    btns.new_button(BTN_RESTART,x, cell_size * 2.0, bw, bh, "Restart board");
-    //btns.new_button(BTN_SAVE,x, cell_size * 3.5, bw, bh, "Save board");
-    btns.new_button(BTN_BACK,x, cell_size * 6.0, bw, bh, "Back to Main");
-    btns.new_button(BTN_QUIT,x, cell_size * 7.5, bw, bh, "Quit");
+    btns.new_button(BTN_HINT, x, row(0.0), bw, bh, "Hint");
+    btns.new_button(BTN_NOTES, x, row(1.0), bw, bh, notes_label(false));
+    btns.new_button(BTN_AUTO_NOTES, x, row(2.0), bw, bh, "Auto notes");
+    btns.new_button(BTN_UNDO, x, row(3.0), (bw - bh) * 0.5, bh, "Undo");
+    btns.new_button(BTN_REDO, x + (bw + bh) * 0.5, row(3.0), (bw - bh) * 0.5, bh, "Redo");
+    btns.new_button(BTN_RESTART, x, row(4.0), bw, bh, "Restart board");
+    btns.new_button(BTN_SOLVE, x, row(5.0), bw, bh, "Solve board");
+    btns.new_button(BTN_SAVE, x, row(6.0), (bw - bh) * 0.5, bh, "Save");
+    btns.new_button(BTN_EXPORT, x + (bw + bh) * 0.5, row(6.0), (bw - bh) * 0.5, bh, "Export");
+    btns.new_button(BTN_BACK, x, row(7.0), bw, bh, "Back to Main");
+    btns.new_button(BTN_QUIT, x, row(8.0), bw, bh, "Quit");
 }
@@ -3,7 +3,8 @@ use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::io::prelude::*;
 use std::path::Path;
-use crate::constants::{CELL_SIZE, CONFIG_FILE, GAME_TITLE, SAVE_PATH};
+use sudoku_engine::board::CheckMode;
+use crate::constants::{AUTOSAVE_MOVES, AUTOSAVE_SECONDS, CELL_SIZE, CONFIG_FILE, GAME_TITLE, SAVE_PATH};
 
 #[derive(Debug,Default)]
 pub struct Preferences {
@@ -11,6 +12,14 @@ pub struct Preferences {
     file_name: String,
     pub color_theme: String,
     pub cell_size: f32,
+    pub auto_clean_notes: bool,
+    pub autosave_seconds: u32,
+    pub autosave_moves: u32,
+    pub check_mode: CheckMode,
+    pub mistake_limit: u32,
+    pub highlight_digits: bool,
+    pub highlight_notes: bool,
+    pub highlight_block: bool,
 }
 
 impl Preferences {
@@ -22,6 +31,14 @@ impl Preferences {
             file_name: CONFIG_FILE.to_string(),
             color_theme: "Light".to_string(),
             cell_size: CELL_SIZE as f32,
+            auto_clean_notes: true,
+            autosave_seconds: AUTOSAVE_SECONDS,
+            autosave_moves: AUTOSAVE_MOVES,
+            check_mode: CheckMode::Conflicts,
+            mistake_limit: 0,
+            highlight_digits: true,
+            highlight_notes: true,
+            highlight_block: false,
         };
 
         let mut save_path: String = get_home_dir();
@@ -70,6 +87,14 @@ impl Preferences {
                 match v[0].to_uppercase().as_str() {
                     "CELL_SIZE" => prefs.cell_size = v[1].parse::<f32>().unwrap_or(80.0),
                     "COLOR_THEME" => prefs.color_theme = v[1].to_string(),
+                    "AUTO_CLEAN_NOTES" => prefs.auto_clean_notes = v[1].trim().parse::<bool>().unwrap_or(true),
+                    "AUTOSAVE_SECONDS" => prefs.autosave_seconds = v[1].trim().parse::<u32>().unwrap_or(AUTOSAVE_SECONDS),
+                    "AUTOSAVE_MOVES" => prefs.autosave_moves = v[1].trim().parse::<u32>().unwrap_or(AUTOSAVE_MOVES),
+                    "CHECK_MODE" => prefs.check_mode = CheckMode::from_name(v[1]).unwrap_or_default(),
+                    "MISTAKE_LIMIT" => prefs.mistake_limit = v[1].trim().parse::<u32>().unwrap_or(0),
+                    "HIGHLIGHT_DIGITS" => prefs.highlight_digits = v[1].trim().parse::<bool>().unwrap_or(true),
+                    "HIGHLIGHT_NOTES" => prefs.highlight_notes = v[1].trim().parse::<bool>().unwrap_or(true),
+                    "HIGHLIGHT_BLOCK" => prefs.highlight_block = v[1].trim().parse::<bool>().unwrap_or(false),
                     _ => (),
                 }
 
@@ -95,6 +120,14 @@ impl Preferences {
         //writer.write_fmt(format_args!("File_Name={}\n", self.file_name)).unwrap();
         writer.write_fmt(format_args!("Cell_Size={}\n", self.cell_size)).unwrap();
         writer.write_fmt(format_args!("Color_Theme={}\n", self.color_theme)).unwrap();
+        writer.write_fmt(format_args!("Auto_Clean_Notes={}\n", self.auto_clean_notes)).unwrap();
+        writer.write_fmt(format_args!("Autosave_Seconds={}\n", self.autosave_seconds)).unwrap();
+        writer.write_fmt(format_args!("Autosave_Moves={}\n", self.autosave_moves)).unwrap();
+        writer.write_fmt(format_args!("Check_Mode={}\n", self.check_mode.name())).unwrap();
+        writer.write_fmt(format_args!("Mistake_Limit={}\n", self.mistake_limit)).unwrap();
+        writer.write_fmt(format_args!("Highlight_Digits={}\n", self.highlight_digits)).unwrap();
+        writer.write_fmt(format_args!("Highlight_Notes={}\n", self.highlight_notes)).unwrap();
+        writer.write_fmt(format_args!("Highlight_Block={}\n", self.highlight_block)).unwrap();
 
         writer.flush().unwrap();
 
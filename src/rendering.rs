@@ -4,8 +4,12 @@ use macroquad::{
     prelude::*,
     texture::Texture2D,
 };
+use sudoku_engine::board::SudokuBoard;
+use sudoku_engine::logic::Step;
 use crate::colors::Colors;
-use crate::board::{get_time_from_seconds, SudokuBoard};
+use crate::constants::{BOARD_SIZE, CELEBRATION_TIME, FIELD_SIZE};
+use crate::stats::{Summary, Victory};
+use crate::storage::get_time_from_seconds;
 
 pub struct Rendering {
     b_size: f32,
@@ -40,6 +44,11 @@ impl Rendering {
 
     }
 
+    pub fn resize(&mut self, board_size: f32, cell_size: f32) {
+        self.b_size = board_size;
+        self.c_size = cell_size;
+    }
+
     pub fn sudoku_title(&mut self) {
 
         self.col_title.g += self.title_dir;
@@ -100,7 +109,7 @@ impl Rendering {
                 let offset_x: f32 = (self.c_size - td.width) * 0.5;
                 let offset_y: f32 = (self.c_size - td.height) * 0.5 + td.offset_y;
 
-                if n >= 1 && n <= 9 {
+                if (1..=9).contains(&n) {
 
                     let mut txt_col = self.col.number_default;
                     if board.is_editable(x, y) {
@@ -124,6 +133,16 @@ impl Rendering {
                         }
                     );
 
+                // draw the notes of an empty cell
+                } else {
+
+                    let notes = board.get_notes(x, y);
+                    for value in 1..=9 {
+                        if notes & (1 << value) != 0 {
+                            self.small_number(x, y, value, self.col.number_note);
+                        }
+                    }
+
                 }
             }
         }
@@ -157,7 +176,7 @@ impl Rendering {
         }
     }
 
-    pub fn selection_board(&self, selected_x: i32, selected_y: i32) -> (f32,f32) {
+    pub fn selection_board(&self, selected_x: i32, selected_y: i32, marked: u16) -> (f32,f32) {
 
         // get text dimensions
         let font_size: u16 = (self.c_size / 10.0 * 8.0) as u16;
@@ -168,19 +187,17 @@ impl Rendering {
         // show only if a cell is selected
         if selected_x >= 0 && selected_y >= 0 {
 
-            let sub_x: f32;
-            if selected_x > 5 {
-                sub_x = (selected_x - 3) as f32 * self.c_size;
+            let sub_x: f32 = if selected_x > 5 {
+                (selected_x - 3) as f32 * self.c_size
             } else {
-                sub_x = (selected_x + 1) as f32 * self.c_size;
-            }
+                (selected_x + 1) as f32 * self.c_size
+            };
 
-            let sub_y: f32;
-            if selected_y > 5 {
-                sub_y = (selected_y - 3) as f32 * self.c_size;
+            let sub_y: f32 = if selected_y > 5 {
+                (selected_y - 3) as f32 * self.c_size
             } else {
-                sub_y = (selected_y + 1) as f32 * self.c_size;
-            }
+                (selected_y + 1) as f32 * self.c_size
+            };
 
             // draw the submenu background to select a number
             draw_rectangle(sub_x, sub_y, self.c_size * 3.0, self.c_size * 3.0, self.col.selection_bkgrd);
@@ -213,6 +230,11 @@ impl Rendering {
 
                     let pos_x = sub_x + x as f32 * self.c_size + offset_x;
 
+                    // mark the numbers which are already noted
+                    if marked & (1 << n) != 0 {
+                        draw_rectangle(sub_x + x as f32 * self.c_size, sub_y + y as f32 * self.c_size, self.c_size, self.c_size, self.col.selection_selector);
+                    }
+
                     draw_text_ex(
                         &n.to_string(),
                         pos_x,
@@ -250,7 +272,7 @@ impl Rendering {
 
     pub fn  selection_get_number(&self, mouse_x: f32, mouse_y: f32, menu_x: f32, menu_y: f32) -> u8 {
         let cs3 = self.c_size * 3.0;
-        if mouse_x >= menu_x && mouse_x <= menu_x + cs3 && mouse_y >= menu_y && mouse_y <= mouse_y + cs3 {
+        if mouse_x >= menu_x && mouse_x <= menu_x + cs3 && mouse_y >= menu_y && mouse_y <= menu_y + cs3 {
             let x = ((mouse_x - menu_x) / self.c_size) as i32;
             let y = ((mouse_y - menu_y) / self.c_size) as i32;
             let num: u8 = (y * 3 + x + 1) as u8;
@@ -264,7 +286,7 @@ impl Rendering {
         let x = self.b_size + self.c_size * 0.5;
         let w = self.c_size * 3.0;
         let h = self.c_size * 0.75;
-        let y = (self.b_size - h * 0.5) * 0.5;
+        let y = self.c_size * 0.2;
 
         draw_rectangle(x, y, w, h,  self.col.btn_bkgrd_dk);
         draw_rectangle(x + 1.0, y + 1.0, w - 2.0, h - 2.0,  self.col.btn_bkgrd_md);
@@ -292,7 +314,379 @@ impl Rendering {
         );
     }
 
-    pub fn button(&self, x: f32, y: f32, w: f32, h: f32, txt_x: f32, txt_y: f32, txt_size: u16, txt: &String, selected: bool) {
+    pub fn list_rows(&self) -> usize {
+        ((self.b_size - self.c_size) / (self.c_size * 0.6)) as usize
+    }
+
+    pub fn list_index(&self, mouse_x: f32, mouse_y: f32, first: usize) -> Option<usize> {
+        let top = self.c_size;
+        if mouse_x < 0.0 || mouse_x > self.b_size || mouse_y < top || mouse_y > self.b_size {
+            return None;
+        }
+        let row = ((mouse_y - top) / (self.c_size * 0.6)) as usize;
+        if row >= self.list_rows() {
+            return None;
+        }
+        Some(first + row)
+    }
+
+    pub fn list(&self, title: &str, lines: &[String], selected: usize, first: usize, mouse_x: f32, mouse_y: f32) {
+
+        let row_h = self.c_size * 0.6;
+        let top = self.c_size;
+
+        self.cover_board(title);
+
+        let font_size = (row_h * 0.5) as u16;
+        let hover = self.list_index(mouse_x, mouse_y, first);
+
+        for (row, i) in (first..lines.len()).take(self.list_rows()).enumerate() {
+
+            let y = top + row as f32 * row_h;
+
+            // mark the selected and the hovered line
+            if i == selected {
+                draw_rectangle(0.0, y, self.b_size, row_h, self.col.cell_selector);
+            }
+            if hover == Some(i) {
+                draw_rectangle(0.0, y, self.b_size, row_h, self.col.selection_selector);
+            }
+
+            let td = measure_text(&lines[i], Some(&self.number_font), font_size, 1.0);
+            draw_text_ex(
+                &lines[i],
+                self.c_size * 0.25,
+                y + (row_h - td.height) * 0.5 + td.offset_y,
+                TextParams {
+                    font_size,
+                    font: Some(&self.number_font),
+                    color: self.col.number_default,
+                    ..Default::default()
+                }
+            );
+
+        }
+
+    }
+
+    pub fn celebration(&self, elapsed: f64) {
+
+        // a wave of coloured cells runs from the middle of the board to the border
+        let wave = (elapsed / CELEBRATION_TIME) as f32 * BOARD_SIZE as f32;
+        let center = (BOARD_SIZE as f32 - 1.0) * 0.5;
+
+        for x in 0..BOARD_SIZE {
+            for y in 0..BOARD_SIZE {
+                let distance = ((x as f32 - center).powi(2) + (y as f32 - center).powi(2)).sqrt();
+                let alpha = 1.0 - (distance - wave).abs() / 1.5;
+                if alpha > 0.0 {
+                    let mut color = self.col.celebration;
+                    color.a *= alpha;
+                    draw_rectangle(x as f32 * self.c_size, y as f32 * self.c_size, self.c_size, self.c_size, color);
+                }
+            }
+        }
+
+    }
+
+    pub fn victory(&self, victory: &Victory) {
+
+        let w = self.c_size * 6.0;
+        let h = self.c_size * 3.5;
+        let x = (self.b_size - w) * 0.5;
+        let y = (self.b_size - h) * 0.5;
+        let line_h = self.c_size * 0.6;
+
+        draw_rectangle(x, y, w, h, self.col.victory_bkgrd);
+        draw_rectangle_lines(x, y, w, h, 3.0, self.col.victory_border);
+
+        let (hour, min, sec) = get_time_from_seconds(victory.result.duration);
+        let best = match victory.best {
+            _ if victory.new_best() => "New best time!".to_string(),
+            Some(best) => {
+                let (b_hour, b_min, b_sec) = get_time_from_seconds(best);
+                format!("Best time {}:{:02}:{:02}", b_hour, b_min, b_sec)
+            }
+            None => String::new(),
+        };
+        let lines = [
+            format!("Time {}:{:02}:{:02}", hour, min, sec),
+            format!("Hints {}, mistakes {}", victory.result.hints, victory.result.mistakes),
+            best,
+        ];
+
+        let title = "Puzzle solved!";
+        let title_size = (self.c_size * 0.5) as u16;
+        let td = measure_text(title, Some(&self.number_font), title_size, 1.0);
+        draw_text_ex(
+            title,
+            x + (w - td.width) * 0.5,
+            y + self.c_size * 0.25 + td.offset_y,
+            TextParams {
+                font_size: title_size,
+                font: Some(&self.number_font),
+                color: self.col.victory_border,
+                ..Default::default()
+            }
+        );
+
+        for (i, line) in lines.iter().enumerate() {
+            self.text_line(line, x + self.c_size * 0.5, y + self.c_size * 1.1 + i as f32 * line_h, line_h, self.col.victory_text);
+        }
+
+    }
+
+    pub fn statistics(&self, summaries: &[Summary]) {
+
+        let top = self.c_size;
+        let left = self.c_size * 0.25;
+        let section_h = (self.b_size - top) / summaries.len().max(1) as f32;
+        let line_h = self.c_size * 0.45;
+
+        self.cover_board("Statistics");
+
+        for (i, summary) in summaries.iter().enumerate() {
+
+            let y = top + i as f32 * section_h;
+            if i > 0 {
+                draw_line(left, y, self.b_size - left, y, 1.0, self.col.cell_line_thin);
+            }
+
+            let rate = (summary.completed * 100).checked_div(summary.played).unwrap_or_default();
+            let played = format!("{}: {} played, {} completed ({}%)", summary.name, summary.played, summary.completed, rate);
+            let times = match (summary.best, summary.average) {
+                (Some(best), Some(average)) => {
+                    let (b_hour, b_min, b_sec) = get_time_from_seconds(best);
+                    let (a_hour, a_min, a_sec) = get_time_from_seconds(average);
+                    format!("best {}:{:02}:{:02}, average {}:{:02}:{:02}, bars of {} min",
+                            b_hour, b_min, b_sec, a_hour, a_min, a_sec, summary.bin_secs / 60)
+                }
+                _ => "no completed game yet".to_string(),
+            };
+            self.text_line(&played, left, y, line_h, self.col.number_default);
+            self.text_line(&times, left, y + line_h, line_h, self.col.number_note);
+
+            // the histogram of the solve times below the text
+            let max = summary.histogram.iter().max().copied().unwrap_or_default();
+            if max == 0 {
+                continue;
+            }
+            let chart_y = y + line_h * 2.0;
+            let chart_h = section_h - line_h * 2.0 - self.c_size * 0.1;
+            let bar_w = (self.b_size - left * 2.0) / summary.histogram.len() as f32;
+            for (bin, count) in summary.histogram.iter().enumerate() {
+                let h = chart_h * *count as f32 / max as f32;
+                draw_rectangle(left + bin as f32 * bar_w + 1.0, chart_y + chart_h - h, bar_w - 2.0, h, self.col.number_editable);
+            }
+            draw_line(left, chart_y + chart_h, self.b_size - left, chart_y + chart_h, 1.0, self.col.board_border);
+
+        }
+
+    }
+
+    pub fn text_page(&self, title: &str, lines: &[&str]) {
+
+        let line_h = self.c_size * 0.5;
+
+        self.cover_board(title);
+
+        for (i, line) in lines.iter().enumerate() {
+            self.text_line(line, self.c_size * 0.25, self.c_size + i as f32 * line_h, line_h, self.col.number_default);
+        }
+
+    }
+
+    fn cover_board(&self, title: &str) {
+
+        let top = self.c_size;
+
+        draw_rectangle(0.0, 0.0, self.b_size, self.b_size, self.col.board_bkgrd);
+        draw_rectangle_lines(0.0, 0.0, self.b_size, self.b_size, 3.0, self.col.board_border);
+
+        let title_size = (self.c_size * 0.45) as u16;
+        let td = measure_text(title, Some(&self.number_font), title_size, 1.0);
+        draw_text_ex(
+            title,
+            (self.b_size - td.width) * 0.5,
+            (top - td.height) * 0.5 + td.offset_y,
+            TextParams {
+                font_size: title_size,
+                font: Some(&self.number_font),
+                color: self.col.number_default,
+                ..Default::default()
+            }
+        );
+
+    }
+
+    fn text_line(&self, txt: &str, x: f32, y: f32, h: f32, color: Color) {
+        let font_size = (h * 0.65) as u16;
+        let td = measure_text(txt, Some(&self.number_font), font_size, 1.0);
+        draw_text_ex(
+            txt,
+            x,
+            y + (h - td.height) * 0.5 + td.offset_y,
+            TextParams {
+                font_size,
+                font: Some(&self.number_font),
+                color,
+                ..Default::default()
+            }
+        );
+    }
+
+    pub fn message(&self, txt: &str) {
+
+        let w = screen_width();
+        let h = self.c_size * 0.5;
+        let y = self.b_size;
+
+        draw_rectangle(0.0, y, w, h, self.col.wnd_bkgrd);
+        draw_line(0.0, y, w, y, 3.0, self.col.board_border);
+
+        let font_size = (self.c_size * 0.25) as u16;
+        let td = measure_text(txt, Some(&self.number_font), font_size, 1.0);
+        let offset_y: f32 = (h - td.height) * 0.5 + td.offset_y;
+
+        draw_text_ex(
+            txt,
+            self.c_size * 0.25,
+            y + offset_y,
+            TextParams {
+                font_size,
+                font: Some(&self.number_font),
+                color: self.col.status_text,
+                ..Default::default()
+            }
+        );
+
+    }
+
+    pub fn conflicts(&self, board: &SudokuBoard) {
+
+        let size = BOARD_SIZE as usize;
+        let field = FIELD_SIZE as usize;
+        let (mut rows, mut cols, mut blocks): (u16, u16, u16) = (0, 0, 0);
+        let mut cells = [[false; BOARD_SIZE as usize]; BOARD_SIZE as usize];
+
+        // collect the units and cells of every clash of a number of the player
+        for y in 0..size {
+            for x in 0..size {
+                if !board.is_editable(x, y) {
+                    continue;
+                }
+                for (px, py) in board.conflicts(x, y) {
+                    if py == y { rows |= 1 << y; }
+                    if px == x { cols |= 1 << x; }
+                    if px / field == x / field && py / field == y / field { blocks |= 1 << (y / field * field + x / field); }
+                    cells[x][y] = true;
+                    cells[px][py] = true;
+                }
+            }
+        }
+
+        // every unit is drawn once, the transparent colour would add up otherwise
+        let board_len = self.c_size * size as f32;
+        let block_len = self.c_size * field as f32;
+        for i in 0..size {
+            if rows & (1 << i) != 0 {
+                draw_rectangle(0.0, i as f32 * self.c_size, board_len, self.c_size, self.col.conflict_unit);
+            }
+            if cols & (1 << i) != 0 {
+                draw_rectangle(i as f32 * self.c_size, 0.0, self.c_size, board_len, self.col.conflict_unit);
+            }
+            if blocks & (1 << i) != 0 {
+                draw_rectangle((i % field) as f32 * block_len, (i / field) as f32 * block_len, block_len, block_len, self.col.conflict_unit);
+            }
+        }
+
+        for (x, column) in cells.iter().enumerate() {
+            for (y, marked) in column.iter().enumerate() {
+                if *marked {
+                    draw_rectangle(x as f32 * self.c_size, y as f32 * self.c_size, self.c_size, self.c_size, self.col.conflict_cell);
+                }
+            }
+        }
+
+    }
+
+    pub fn highlights(&self, board: &SudokuBoard, x: usize, y: usize, digits: bool, notes: bool, block: bool) {
+
+        let small = self.c_size / 3.0;
+        let block_len = self.c_size * FIELD_SIZE as f32;
+
+        // the block of the cell
+        if block {
+            let (block_x, block_y) = ((x / FIELD_SIZE as usize) as f32, (y / FIELD_SIZE as usize) as f32);
+            draw_rectangle(block_x * block_len, block_y * block_len, block_len, block_len, self.col.highlight_block);
+        }
+
+        // the same number in other cells and in the notes of empty cells
+        let value = board.get_field(x, y);
+        if value == 0 {
+            return;
+        }
+        for cell_y in 0..BOARD_SIZE as usize {
+            for cell_x in 0..BOARD_SIZE as usize {
+                let pos_x = cell_x as f32 * self.c_size;
+                let pos_y = cell_y as f32 * self.c_size;
+                if digits && board.get_field(cell_x, cell_y) == value {
+                    draw_rectangle(pos_x, pos_y, self.c_size, self.c_size, self.col.highlight_digit);
+                } else if notes && board.get_field(cell_x, cell_y) == 0 && board.get_notes(cell_x, cell_y) & (1 << value) != 0 {
+                    let note_x = pos_x + ((value - 1) % 3) as f32 * small;
+                    let note_y = pos_y + ((value - 1) / 3) as f32 * small;
+                    draw_rectangle(note_x, note_y, small, small, self.col.highlight_note);
+                }
+            }
+        }
+
+    }
+
+    pub fn hint(&self, step: &Step) {
+
+        // mark all cells which explain the deduction
+        for &(x, y) in step.cells.iter() {
+            draw_rectangle(x as f32 * self.c_size, y as f32 * self.c_size, self.c_size, self.c_size, self.col.hint_cell);
+        }
+
+        // mark the cells where a number can be placed
+        for &(x, y, _) in step.placements.iter() {
+            draw_rectangle(x as f32 * self.c_size, y as f32 * self.c_size, self.c_size, self.c_size, self.col.hint_target);
+        }
+
+        // draw the removed candidates small at their position inside the cell
+        for &(x, y, value) in step.eliminations.iter() {
+            self.small_number(x, y, value, self.col.hint_candidate);
+        }
+
+    }
+
+    fn small_number(&self, x: usize, y: usize, value: u8, color: Color) {
+
+        let small = self.c_size / 3.0;
+        let font_size = (small * 0.8) as u16;
+
+        let n = value.to_string();
+        let td = measure_text(&n, Some(&self.number_font), font_size, 1.0);
+        let pos_x = x as f32 * self.c_size + ((value - 1) % 3) as f32 * small + (small - td.width) * 0.5;
+        let pos_y = y as f32 * self.c_size + ((value - 1) / 3) as f32 * small + (small - td.height) * 0.5 + td.offset_y;
+
+        draw_text_ex(
+            &n,
+            pos_x,
+            pos_y,
+            TextParams {
+                font_size,
+                font: Some(&self.number_font),
+                color,
+                ..Default::default()
+            }
+        );
+
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn button(&self, x: f32, y: f32, w: f32, h: f32, txt_x: f32, txt_y: f32, txt_size: u16, txt: &str, selected: bool) {
 
         let text_color: Color;
         let btn_color: Color;
@@ -0,0 +1,192 @@
+use std::fs;
+use std::path::Path;
+
+use sudoku_engine::logic::Difficulty;
+
+use crate::constants::*;
+use crate::daily::today;
+use crate::preferences::get_home_dir;
+use crate::storage::write_atomic;
+
+// the number of bars of the time histogram
+const HISTOGRAM_BINS: usize = 10;
+
+// one finished game, solved by the player, given up with the solver or lost by too many mistakes
+#[derive(Clone, Debug)]
+pub struct GameResult {
+    pub day: u64,
+    pub difficulty: Option<Difficulty>,
+    pub duration: u64,
+    pub hints: u32,
+    pub mistakes: u32,
+    pub solved: bool,
+    pub solver: bool,
+}
+
+// the summary of a game just solved by the player
+#[derive(Clone, Debug)]
+pub struct Victory {
+    pub result: GameResult,
+    pub best: Option<u64>,
+    pub time: f64,
+    pub visible: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Stats {
+    games: Vec<GameResult>,
+}
+
+// the statistics of all games with the same difficulty
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub name: String,
+    pub played: u32,
+    pub completed: u32,
+    pub best: Option<u64>,
+    pub average: Option<u64>,
+    pub histogram: Vec<u32>,
+    pub bin_secs: u64,
+}
+
+impl GameResult {
+
+    pub fn new(difficulty: Option<Difficulty>, duration: u64, hints: u32, mistakes: u32, solved: bool, solver: bool) -> GameResult {
+        GameResult {
+            day: today(),
+            difficulty,
+            duration,
+            hints,
+            mistakes,
+            solved,
+            solver,
+        }
+    }
+
+    fn parse(line: &str) -> Option<GameResult> {
+        let v: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
+        if v.len() != 7 {
+            return None;
+        }
+        Some(GameResult {
+            day: v[0].parse::<u64>().ok()?,
+            difficulty: Difficulty::from_name(v[1]),
+            duration: v[2].parse::<u64>().ok()?,
+            hints: v[3].parse::<u32>().ok()?,
+            mistakes: v[4].parse::<u32>().ok()?,
+            solved: v[5].parse::<bool>().ok()?,
+            solver: v[6].parse::<bool>().ok()?,
+        })
+    }
+
+    fn to_line(&self) -> String {
+        let difficulty = self.difficulty.map_or("-", |d| d.name());
+        format!("{},{},{},{},{},{},{}\n", self.day, difficulty, self.duration, self.hints, self.mistakes, self.solved, self.solver)
+    }
+
+}
+
+impl Victory {
+
+    pub fn new_best(&self) -> bool {
+        self.best.is_none_or(|best| self.result.duration < best)
+    }
+
+}
+
+impl Stats {
+
+    pub fn load() -> Stats {
+
+        let mut stats = Stats::default();
+        let path_name = Path::new(&get_home_dir()).join(SAVE_PATH).join(STATS_FILE);
+
+        let text = match fs::read_to_string(&path_name) {
+            Ok(text) => text,
+            // no game finished yet
+            Err(_) => return stats,
+        };
+
+        for line in text.lines() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match GameResult::parse(line) {
+                Some(result) => stats.games.push(result),
+                None => eprintln!("Skipping line in statistics: {}", line),
+            }
+        }
+
+        stats
+
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let mut text = format!("# {} statistics: day,difficulty,seconds,hints,mistakes,solved,solver\n", GAME_TITLE);
+        for result in self.games.iter() {
+            text.push_str(&result.to_line());
+        }
+        let path_name = Path::new(&get_home_dir()).join(SAVE_PATH).join(STATS_FILE);
+        write_atomic(&path_name, &text).map_err(|err| format!("Can't save statistics: {}", err))
+    }
+
+    pub fn record(&mut self, result: GameResult) {
+        self.games.push(result);
+    }
+
+    pub fn best_time(&self, difficulty: Option<Difficulty>) -> Option<u64> {
+        self.games.iter()
+            .filter(|g| g.solved && g.difficulty == difficulty)
+            .map(|g| g.duration)
+            .min()
+    }
+
+    pub fn summaries(&self) -> Vec<Summary> {
+
+        let mut result: Vec<Summary> = [Difficulty::Easy, Difficulty::Moderate, Difficulty::Hard].iter()
+            .map(|d| self.summary(Some(*d), d.name()))
+            .collect();
+
+        // imported puzzles without a difficulty only show up if there are any
+        let other = self.summary(None, "Other");
+        if other.played > 0 {
+            result.push(other);
+        }
+
+        result
+
+    }
+
+    fn summary(&self, difficulty: Option<Difficulty>, name: &str) -> Summary {
+
+        let games: Vec<&GameResult> = self.games.iter().filter(|g| g.difficulty == difficulty).collect();
+        let times: Vec<u64> = games.iter().filter(|g| g.solved).map(|g| g.duration).collect();
+
+        let mut summary = Summary {
+            name: name.to_string(),
+            played: games.len() as u32,
+            completed: times.len() as u32,
+            best: times.iter().min().copied(),
+            average: None,
+            histogram: vec![0; HISTOGRAM_BINS],
+            bin_secs: 60,
+        };
+
+        if times.is_empty() {
+            return summary;
+        }
+        summary.average = Some(times.iter().sum::<u64>() / times.len() as u64);
+
+        // the bars are whole minutes wide and cover the slowest game
+        let slowest = times.iter().max().copied().unwrap_or_default();
+        summary.bin_secs = (slowest / HISTOGRAM_BINS as u64 / 60 + 1) * 60;
+        for secs in times.iter() {
+            let bin = ((secs / summary.bin_secs) as usize).min(HISTOGRAM_BINS - 1);
+            summary.histogram[bin] += 1;
+        }
+
+        summary
+
+    }
+
+}
@@ -0,0 +1,242 @@
+use std::fs;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use sudoku_engine::board::SudokuBoard;
+use sudoku_engine::formats::parse_puzzles;
+use sudoku_engine::logic::Difficulty;
+use sudoku_engine::savegame::{SaveError, SaveGame};
+
+use crate::constants::*;
+use crate::preferences::get_home_dir;
+
+pub fn load_game(board: &mut SudokuBoard, file_name: String) -> Result<u64, SaveError> {
+
+    let full_path: PathBuf = Path::new(&get_home_dir()).join(SAVE_PATH).join(file_name);
+
+    let text = match fs::read_to_string(&full_path) {
+        Ok(text)    => text,
+        Err(err)    => return Err(SaveError::Io(format!("Can't open sudoku [{}]: {}.", full_path.display(), err)))
+    };
+
+    let game = SaveGame::parse(&text)?;
+    Ok(board.restore_savegame(&game))
+
+}
+
+pub fn save_game(board: &SudokuBoard, duration: u64, default_name: bool) -> Result<(), String> {
+
+    let file_name: String = if default_name {
+        autosave_name()
+    } else {
+        timestamp_name(FILE_EXT)
+    };
+
+    let path_name = Path::new(&get_home_dir()).join(SAVE_PATH).join(file_name);
+    write_atomic(&path_name, &board.to_savegame(duration).to_text())
+        .map_err(|err| format!("Can't save sudoku: {}", err))
+
+}
+
+pub fn import_puzzle(board: &mut SudokuBoard, file_name: &str, index: usize) -> Result<(), String> {
+
+    let full_path: PathBuf = Path::new(&get_home_dir()).join(SAVE_PATH).join(file_name);
+
+    let text = match fs::read_to_string(&full_path) {
+        Ok(text)    => text,
+        Err(err)    => return Err(format!("Can't open puzzle [{}]: {}.", full_path.display(), err))
+    };
+
+    let puzzles = parse_puzzles(&text)?;
+    match puzzles.get(index) {
+        Some(grid) => board.set_puzzle(grid),
+        None => Err(format!("{} has no puzzle {}", file_name, index + 1)),
+    }
+
+}
+
+pub fn export_board(board: &SudokuBoard) -> Result<String, String> {
+
+    let export_dir = Path::new(&get_home_dir()).join(SAVE_PATH).join(EXPORT_PATH);
+    if !export_dir.exists() && let Err(err) = fs::create_dir(&export_dir) {
+        return Err(format!("Can't create export dir [{}]: {}", export_dir.display(), err));
+    }
+
+    let path_name = export_dir.join(timestamp_name("txt"));
+    match write_atomic(&path_name, &board.export_text()) {
+        Ok(_) => Ok(path_name.display().to_string()),
+        Err(err) => Err(format!("Can't export sudoku: {}", err)),
+    }
+
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SaveInfo {
+    pub file_name: String,
+    pub modified: u64,
+    pub duration: u64,
+    pub progress: u32,
+    pub hints: u32,
+    pub difficulty: Option<Difficulty>,
+    // the number of the puzzle in an imported puzzle file
+    pub puzzle: Option<usize>,
+}
+
+pub fn list_saves() -> Vec<SaveInfo> {
+
+    let mut result: Vec<SaveInfo> = Vec::new();
+    let save_dir = Path::new(&get_home_dir()).join(SAVE_PATH);
+
+    let entries = match fs::read_dir(&save_dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("Can't read save dir [{}]: {}", save_dir.display(), err);
+            return result
+        }
+    };
+
+    for entry in entries.flatten() {
+
+        let path = entry.path();
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+        if ext != FILE_EXT && !PUZZLE_EXT.contains(&ext.as_str()) {
+            continue;
+        }
+
+        // the files of the game itself are no puzzles
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name == DAILY_FILE || file_name == STATS_FILE {
+            continue;
+        }
+        let modified = entry.metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_secs());
+
+        // every puzzle of an imported file gets its own line
+        if ext != FILE_EXT {
+            let puzzles = fs::read_to_string(&path).map_err(|err| err.to_string()).and_then(|text| parse_puzzles(&text));
+            match puzzles {
+                Ok(puzzles) => {
+                    for (i, grid) in puzzles.iter().enumerate() {
+                        let givens = grid.iter().flatten().filter(|v| **v != 0).count() as u32;
+                        result.push(SaveInfo {
+                            file_name: file_name.clone(),
+                            modified,
+                            progress: givens * 100 / (BOARD_SIZE * BOARD_SIZE) as u32,
+                            puzzle: Some(i),
+                            ..Default::default()
+                        });
+                    }
+                }
+                Err(err) => eprintln!("Can't read puzzle file [{}]: {}", path.display(), err),
+            }
+            continue;
+        }
+
+        // skip files which can't be read as a sudoku
+        let mut board = SudokuBoard::default();
+        if let Ok(duration) = load_game(&mut board, file_name.clone()) {
+            result.push(SaveInfo {
+                file_name,
+                modified,
+                duration,
+                progress: board.progress(),
+                hints: board.hints_used(),
+                difficulty: board.get_difficulty(),
+                puzzle: None,
+            });
+        }
+
+    }
+
+    // newest games first
+    result.sort_by_key(|info| std::cmp::Reverse(info.modified));
+    result
+
+}
+
+fn timestamp_name(ext: &str) -> String {
+
+    let sys_secs = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let date: (u32,u8,u8) = get_date_from_days(sys_secs / 86_400);
+    let time: (u8,u8,u8) = get_time_from_seconds(sys_secs % 86_400);
+
+    format!("sudoku_{}-{}-{}_{}-{}-{}.{}",
+            date.0, date.1, date.2, time.0, time.1, time.2, ext)
+
+}
+
+pub fn write_atomic(path_name: &Path, text: &str) -> std::io::Result<()> {
+
+    // write a temp file first, so a broken write never replaces a good save
+    let mut tmp_name = path_name.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+
+    let result = File::create(&tmp_path).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        writer.write_all(text.as_bytes())?;
+        writer.into_inner().map_err(|err| err.into_error())?.sync_all()
+    }).and_then(|_| fs::rename(&tmp_path, path_name));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+
+}
+
+pub fn autosave_name() -> String {
+    format!("{}.{}", DEFAULT_FILE, FILE_EXT)
+}
+
+pub fn autosave_exists() -> bool {
+    Path::new(&get_home_dir()).join(SAVE_PATH).join(autosave_name()).exists()
+}
+
+pub fn delete_save(file_name: &str) -> Result<(), String> {
+    let path_name = Path::new(&get_home_dir()).join(SAVE_PATH).join(file_name);
+    fs::remove_file(&path_name).map_err(|err| format!("Can't delete sudoku [{}]: {}", path_name.display(), err))
+}
+
+pub fn get_date_from_days(days: u64) -> (u32,u8,u8) {
+    let z: i64 = days as i64 + 719_468;
+    let era = if z >= 0 {
+        z / 146_097
+    } else {
+        (z - 146_096) / 146_097
+    };
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let mut date: (u32,u8,u8) = (y as u32, m as u8, d as u8);
+    if m <= 2 {
+        date.0 = (y + 1) as u32;
+    }
+    date
+}
+
+pub fn get_time_from_seconds(seconds: u64) -> (u8,u8,u8) {
+    let mut s = seconds;
+    let h = s / 3_600;
+    s -= h * 3_600;
+    let m = s / 60;
+    s -= m * 60;
+    let time: (u8,u8,u8) = (h as u8, m as u8, s as u8);
+    time
+}
+
+
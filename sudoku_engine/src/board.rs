@@ -0,0 +1,820 @@
+use crate::constants::*;
+use crate::formats::{write_puzzle, Format};
+use crate::history::{CellState, Change, History};
+use crate::logic::Difficulty;
+use crate::rng::Rng;
+use crate::savegame::SaveGame;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Symmetry {
+    #[default]
+    None,
+    Rotational,
+    Mirror,
+}
+
+// how the numbers of the player are marked as wrong
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CheckMode {
+    Off,
+    #[default]
+    Conflicts,
+    Solution,
+}
+
+impl CheckMode {
+
+    pub fn from_name(name: &str) -> Option<CheckMode> {
+        match name.trim().to_lowercase().as_str() {
+            "off" => Some(CheckMode::Off),
+            "conflicts" => Some(CheckMode::Conflicts),
+            "solution" => Some(CheckMode::Solution),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CheckMode::Off => "Off",
+            CheckMode::Conflicts => "Conflicts",
+            CheckMode::Solution => "Solution",
+        }
+    }
+
+    pub fn next(&self) -> CheckMode {
+        match self {
+            CheckMode::Off => CheckMode::Conflicts,
+            CheckMode::Conflicts => CheckMode::Solution,
+            CheckMode::Solution => CheckMode::Off,
+        }
+    }
+
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SudokuBoard {
+    board: [[u8; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+    editable: [[bool; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+    valid: [[bool; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+    notes: [[u16; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+    hints: u32,
+    mistakes: u32,
+    history: History,
+    difficulty: Option<Difficulty>,
+    seed: Option<u64>,
+    daily: bool,
+    edits: u32,
+    check_mode: CheckMode,
+    snapshot: Option<[[CellState; BOARD_SIZE as usize]; BOARD_SIZE as usize]>,
+}
+
+impl SudokuBoard {
+
+    pub fn reset(&mut self) {
+        for y in 0..BOARD_SIZE as u8 {
+            for x in 0..BOARD_SIZE as u8 {
+                if self.editable[x as usize][y as usize] {
+                    self.board[x as usize][y as usize] = 0;
+                }
+                self.valid[x as usize][y as usize] = false;
+                self.notes[x as usize][y as usize] = 0;
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.hints = 0;
+        self.mistakes = 0;
+        self.history.clear();
+        self.difficulty = None;
+        self.seed = None;
+        self.daily = false;
+        self.snapshot = None;
+        for y in 0..BOARD_SIZE as u8 {
+            for x in 0..BOARD_SIZE as u8 {
+                self.board[x as usize][y as usize] = 0;
+                self.editable[x as usize][y as usize] = false;
+                self.valid[x as usize][y as usize] = false;
+                self.notes[x as usize][y as usize] = 0;
+            }
+        }
+    }
+
+    pub fn get_field(&self, x: usize, y: usize) -> u8 {
+        self.board[x][y]
+    }
+
+    pub fn set_field(&mut self, x: usize, y: usize, value: u8) {
+        self.board[x][y] = value;
+    }
+
+    pub fn was_valid(&self, x: usize, y: usize) -> bool {
+        self.valid[x][y]
+    }
+
+    pub fn is_editable(&self, x: usize, y: usize) -> bool {
+        self.editable[x][y]
+    }
+
+    pub fn get_notes(&self, x: usize, y: usize) -> u16 {
+        self.notes[x][y]
+    }
+
+    pub fn toggle_note(&mut self, x: usize, y: usize, value: u8) {
+        self.notes[x][y] ^= 1 << value;
+    }
+
+    pub fn enter_number(&mut self, x: usize, y: usize, value: u8, clean_notes: bool) {
+        // the same number again clears the cell
+        if value == 0 || self.board[x][y] == value {
+            self.board[x][y] = 0;
+            self.check_numbers();
+            return;
+        }
+        self.board[x][y] = value;
+        self.check_numbers();
+        // without a check mode nothing is marked, so nothing counts as mistake,
+        // switching the mode on later mustn't lose the game at once
+        let mistake = match self.check_mode {
+            CheckMode::Off => false,
+            CheckMode::Conflicts => self.has_conflict(x, y),
+            CheckMode::Solution => !self.valid[x][y],
+        };
+        if mistake {
+            self.mistakes += 1;
+        }
+        if clean_notes {
+            self.remove_note_from_peers(x, y, value);
+        }
+    }
+
+    pub fn clear_cell(&mut self, x: usize, y: usize) {
+        // first the number, than the notes of the cell
+        if self.board[x][y] != 0 {
+            self.board[x][y] = 0;
+            self.check_numbers();
+        } else {
+            self.notes[x][y] = 0;
+        }
+    }
+
+    pub fn enter_note(&mut self, x: usize, y: usize, value: u8) {
+        // notes are only visible in an empty cell
+        if self.board[x][y] != 0 {
+            self.board[x][y] = 0;
+            self.check_numbers();
+        }
+        self.toggle_note(x, y, value);
+    }
+
+    pub fn get_check_mode(&self) -> CheckMode {
+        self.check_mode
+    }
+
+    pub fn set_check_mode(&mut self, check_mode: CheckMode) {
+        self.check_mode = check_mode;
+        self.check_numbers();
+    }
+
+    pub fn check_numbers(&mut self) {
+
+        // without a unique solution only the conflicts can be checked
+        let solution = if self.check_mode == CheckMode::Solution && self.givens().has_unique_solution() {
+            self.solution()
+        } else {
+            None
+        };
+
+        for y in 0..BOARD_SIZE as usize {
+            for x in 0..BOARD_SIZE as usize {
+                let value = self.board[x][y];
+                self.valid[x][y] = if value == 0 || !self.editable[x][y] {
+                    false
+                } else if self.check_mode == CheckMode::Off {
+                    true
+                } else if let Some(solution) = &solution {
+                    solution.board[x][y] == value
+                } else {
+                    !self.has_conflict(x, y)
+                };
+            }
+        }
+
+    }
+
+    pub fn has_conflict(&self, x: usize, y: usize) -> bool {
+        !self.conflicts(x, y).is_empty()
+    }
+
+    pub fn conflicts(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+
+        // all cells of the row, column and block with the same number
+        let mut result: Vec<(usize, usize)> = Vec::new();
+        let value = self.board[x][y];
+        if value == 0 {
+            return result;
+        }
+
+        let start_x: usize = x - x % FIELD_SIZE as usize;
+        let start_y: usize = y - y % FIELD_SIZE as usize;
+        for i in 0..BOARD_SIZE as usize {
+            let block = (start_x + i % FIELD_SIZE as usize, start_y + i / FIELD_SIZE as usize);
+            for (px, py) in [(i, y), (x, i), block] {
+                if (px, py) != (x, y) && self.board[px][py] == value && !result.contains(&(px, py)) {
+                    result.push((px, py));
+                }
+            }
+        }
+
+        result
+
+    }
+
+    pub fn fill_candidates(&mut self) {
+        for y in 0..BOARD_SIZE as usize {
+            for x in 0..BOARD_SIZE as usize {
+                if self.board[x][y] == 0 {
+                    self.notes[x][y] = self.candidates(x, y);
+                }
+            }
+        }
+    }
+
+    pub fn remove_note_from_peers(&mut self, x: usize, y: usize, value: u8) {
+        let start_x: usize = x - x % FIELD_SIZE as usize;
+        let start_y: usize = y - y % FIELD_SIZE as usize;
+        for i in 0..BOARD_SIZE as usize {
+            self.notes[i][y] &= !(1 << value);
+            self.notes[x][i] &= !(1 << value);
+            self.notes[start_x + i % FIELD_SIZE as usize][start_y + i / FIELD_SIZE as usize] &= !(1 << value);
+        }
+    }
+
+    fn cell_state(&self, x: usize, y: usize) -> CellState {
+        CellState {
+            value: self.board[x][y],
+            notes: self.notes[x][y],
+            valid: self.valid[x][y],
+        }
+    }
+
+    fn set_cell_state(&mut self, x: usize, y: usize, state: CellState) {
+        self.board[x][y] = state.value;
+        self.notes[x][y] = state.notes;
+        self.valid[x][y] = state.valid;
+    }
+
+    pub fn begin_move(&mut self) {
+        self.snapshot = Some(std::array::from_fn(|x| std::array::from_fn(|y| self.cell_state(x, y))));
+    }
+
+    pub fn end_move(&mut self) {
+        let Some(cells) = self.snapshot.take() else {
+            return;
+        };
+        // store every cell which was changed since the move began
+        let mut changes: Vec<Change> = Vec::new();
+        for (x, col) in cells.iter().enumerate() {
+            for (y, &before) in col.iter().enumerate() {
+                let after = self.cell_state(x, y);
+                if before != after {
+                    changes.push(Change { x, y, before, after });
+                }
+            }
+        }
+        if !changes.is_empty() {
+            self.edits += 1;
+        }
+        self.history.push(changes);
+    }
+
+    pub fn undo(&mut self) -> bool {
+        let Some(changes) = self.history.undo().cloned() else {
+            return false;
+        };
+        self.edits += 1;
+        for c in changes {
+            self.set_cell_state(c.x, c.y, c.before);
+        }
+        self.check_numbers();
+        true
+    }
+
+    pub fn redo(&mut self) -> bool {
+        let Some(changes) = self.history.redo().cloned() else {
+            return false;
+        };
+        self.edits += 1;
+        for c in changes {
+            self.set_cell_state(c.x, c.y, c.after);
+        }
+        self.check_numbers();
+        true
+    }
+
+    pub fn add_hint(&mut self) {
+        self.hints += 1;
+    }
+
+    pub fn hints_used(&self) -> u32 {
+        self.hints
+    }
+
+    pub fn mistakes_made(&self) -> u32 {
+        self.mistakes
+    }
+
+    pub fn edit_count(&self) -> u32 {
+        // counts moves, undos and redos, it isn't reset by a new game
+        self.edits
+    }
+
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    pub fn is_daily(&self) -> bool {
+        self.daily
+    }
+
+    pub fn set_daily(&mut self, daily: bool) {
+        self.daily = daily;
+    }
+
+    pub fn get_difficulty(&self) -> Option<Difficulty> {
+        self.difficulty
+    }
+
+    pub fn set_difficulty(&mut self, difficulty: Option<Difficulty>) {
+        self.difficulty = difficulty;
+    }
+
+    fn is_in_row(&self, y: usize, value: u8) -> bool {
+        for x in 0..9 {
+            if self.get_field(x, y) == value {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn is_in_col(&self, x: usize, value: u8) -> bool {
+        for y in 0..9 {
+            if self.get_field(x, y) == value {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn is_in_block(&self, x: usize, y: usize, value: u8) -> bool {
+        let start_x: usize = x - x % FIELD_SIZE as usize;
+        let start_y: usize = y - y % FIELD_SIZE as usize;
+        for y in 0..FIELD_SIZE as usize {
+            for x in 0..FIELD_SIZE as usize {
+                if self.get_field(start_x + x, start_y + y) == value {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn next_empty_field(&self) -> Option<(usize, usize)> {
+
+        for col in 0..BOARD_SIZE {
+
+            for row in 0..BOARD_SIZE {
+
+                if self.get_field(row as usize, col as usize) == 0 {
+                    return Some((row as usize, col as usize));
+                }
+
+            }
+
+        }
+
+        None
+
+    }
+
+    pub fn is_valid_move(&self, x: usize, y: usize, value: u8) -> bool {
+        if !self.is_in_row(y, value) && !self.is_in_col(x, value) && !self.is_in_block(x, y, value) {
+            return true;
+        }
+        false
+    }
+
+    pub fn solve(&mut self) -> bool {
+
+        if let Some((row, col)) = self.next_empty_field() {
+
+            for value in 1..=9  {
+
+                if self.is_valid_move(row, col, value as u8) {
+
+                    self.set_field(row, col, value as u8);
+
+                    if self.solve() {
+                        return true;
+                    }
+
+                    self.set_field(row, col, 0);
+
+                }
+
+            }
+
+            return  false;
+
+        }
+
+        true
+
+    }
+
+    pub fn candidates(&self, x: usize, y: usize) -> u16 {
+        let mut mask: u16 = 0;
+        for value in 1..=BOARD_SIZE as u8 {
+            if self.is_valid_move(x, y, value) {
+                mask |= 1 << value;
+            }
+        }
+        mask
+    }
+
+    fn unit_masks(&self) -> Option<[u16; 27]> {
+        // rows, columns and blocks with all used numbers as bits
+        let mut used: [u16; 27] = [0; 27];
+        for y in 0..BOARD_SIZE as usize {
+            for x in 0..BOARD_SIZE as usize {
+                let value = self.board[x][y];
+                if value == 0 {
+                    continue;
+                }
+                let bit: u16 = 1 << value;
+                let units = SudokuBoard::units_of(x, y);
+                if units.iter().any(|&u| used[u] & bit != 0) {
+                    return None;
+                }
+                units.iter().for_each(|&u| used[u] |= bit);
+            }
+        }
+        Some(used)
+    }
+
+    fn units_of(x: usize, y: usize) -> [usize; 3] {
+        let block = (y / FIELD_SIZE as usize) * FIELD_SIZE as usize + x / FIELD_SIZE as usize;
+        [y, BOARD_SIZE as usize + x, 2 * BOARD_SIZE as usize + block]
+    }
+
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        // a board with duplicate numbers has no solution at all
+        let Some(mut used) = self.unit_masks() else {
+            return 0;
+        };
+        let mut grid = self.clone();
+        let mut count: usize = 0;
+        grid.count_recursive(&mut used, limit, &mut count, &mut None);
+        count
+    }
+
+    pub fn solution(&self) -> Option<SudokuBoard> {
+        let mut grid = self.givens();
+        let mut used = grid.unit_masks()?;
+        let mut count: usize = 0;
+        let mut first: Option<SudokuBoard> = None;
+        grid.count_recursive(&mut used, 1, &mut count, &mut first);
+        first
+    }
+
+    pub fn fill_solution(&mut self) -> bool {
+        // the solution comes from the givens, so wrong numbers of the player are replaced too
+        let Some(solution) = self.solution() else {
+            return false;
+        };
+        self.board = solution.board;
+        self.check_numbers();
+        true
+    }
+
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
+    pub fn givens(&self) -> SudokuBoard {
+        let mut result = SudokuBoard::default();
+        for y in 0..BOARD_SIZE as usize {
+            for x in 0..BOARD_SIZE as usize {
+                if !self.editable[x][y] {
+                    result.board[x][y] = self.board[x][y];
+                }
+            }
+        }
+        result
+    }
+
+    fn count_recursive(&mut self, used: &mut [u16; 27], limit: usize, count: &mut usize, first: &mut Option<SudokuBoard>) {
+
+        // search the empty field with the fewest candidates
+        let mut best: Option<(usize, usize, u16)> = None;
+        for y in 0..BOARD_SIZE as usize {
+            for x in 0..BOARD_SIZE as usize {
+                if self.board[x][y] != 0 {
+                    continue;
+                }
+                let units = SudokuBoard::units_of(x, y);
+                let mask = !(used[units[0]] | used[units[1]] | used[units[2]]) & 0x3fe;
+                if mask == 0 {
+                    return;
+                }
+                if best.is_none_or(|(_, _, m)| mask.count_ones() < m.count_ones()) {
+                    best = Some((x, y, mask));
+                }
+            }
+        }
+
+        // no empty field left, we found a solution
+        let Some((x, y, mask)) = best else {
+            if first.is_none() {
+                *first = Some(self.clone());
+            }
+            *count += 1;
+            return;
+        };
+
+        let units = SudokuBoard::units_of(x, y);
+        for value in 1..=BOARD_SIZE as u8 {
+            let bit: u16 = 1 << value;
+            if mask & bit != 0 {
+                self.board[x][y] = value;
+                units.iter().for_each(|&u| used[u] |= bit);
+                self.count_recursive(used, limit, count, first);
+                units.iter().for_each(|&u| used[u] &= !bit);
+                self.board[x][y] = 0;
+                if *count >= limit {
+                    return;
+                }
+            }
+        }
+
+    }
+
+    fn fill_random(&mut self, rng: &mut Rng) -> bool {
+
+        if let Some((x, y)) = self.next_empty_field() {
+
+            let mut values: Vec<u8> = (1..=BOARD_SIZE as u8).collect();
+            rng.shuffle(&mut values);
+
+            for value in values {
+                if self.is_valid_move(x, y, value) {
+                    self.set_field(x, y, value);
+                    if self.fill_random(rng) {
+                        return true;
+                    }
+                    self.set_field(x, y, 0);
+                }
+            }
+
+            return false;
+
+        }
+
+        true
+
+    }
+
+    fn symmetric_field(x: usize, y: usize, symmetry: Symmetry) -> (usize, usize) {
+        let bsize: usize = BOARD_SIZE as usize;
+        match symmetry {
+            Symmetry::None => (x, y),
+            Symmetry::Rotational => (bsize - x - 1, bsize - y - 1),
+            Symmetry::Mirror => (bsize - x - 1, y),
+        }
+    }
+
+    pub fn generate(&mut self, clues: usize, symmetry: Symmetry, rng: &mut Rng) {
+
+        // create a complete and valid solution
+        self.clear();
+        self.fill_random(rng);
+
+        // get all fields in a random order
+        let mut fields: Vec<(usize, usize)> = Vec::new();
+        for y in 0..BOARD_SIZE as usize {
+            for x in 0..BOARD_SIZE as usize {
+                fields.push((x, y));
+            }
+        }
+        rng.shuffle(&mut fields);
+
+        // remove numbers as long as the board has only one solution
+        let mut remaining: usize = fields.len();
+        for (x, y) in fields {
+
+            if remaining <= clues {
+                break;
+            }
+
+            let (sx, sy) = SudokuBoard::symmetric_field(x, y, symmetry);
+            if self.board[x][y] == 0 || self.board[sx][sy] == 0 {
+                continue;
+            }
+
+            let (value, sym_value) = (self.board[x][y], self.board[sx][sy]);
+            self.board[x][y] = 0;
+            self.board[sx][sy] = 0;
+
+            if self.has_unique_solution() {
+                remaining -= if (x, y) == (sx, sy) { 1 } else { 2 };
+            } else {
+                self.board[x][y] = value;
+                self.board[sx][sy] = sym_value;
+            }
+
+        }
+
+        // the remaining numbers are the givens
+        for y in 0..BOARD_SIZE as usize {
+            for x in 0..BOARD_SIZE as usize {
+                self.editable[x][y] = self.board[x][y] == 0;
+                self.valid[x][y] = false;
+            }
+        }
+
+    }
+
+    pub fn to_savegame(&self, duration: u64) -> SaveGame {
+
+        let mut game = SaveGame {
+            duration,
+            hints: self.hints,
+            mistakes: self.mistakes,
+            difficulty: self.difficulty,
+            seed: self.seed,
+            daily: self.daily,
+            history: self.history.clone(),
+            ..Default::default()
+        };
+
+        for y in 0..BOARD_SIZE as usize {
+            for x in 0..BOARD_SIZE as usize {
+                game.values[x][y] = self.board[x][y];
+                game.givens[x][y] = if self.editable[x][y] { 0 } else { self.board[x][y] };
+                game.valid[x][y] = self.editable[x][y] && self.valid[x][y];
+                game.notes[x][y] = self.notes[x][y];
+            }
+        }
+
+        game
+
+    }
+
+    pub fn restore_savegame(&mut self, game: &SaveGame) -> u64 {
+
+        self.clear();
+        for y in 0..BOARD_SIZE as usize {
+            for x in 0..BOARD_SIZE as usize {
+                self.board[x][y] = game.values[x][y];
+                self.editable[x][y] = game.givens[x][y] == 0;
+                self.valid[x][y] = game.valid[x][y];
+                self.notes[x][y] = game.notes[x][y];
+            }
+        }
+        self.hints = game.hints;
+        self.mistakes = game.mistakes;
+        self.difficulty = game.difficulty;
+        self.seed = game.seed;
+        self.daily = game.daily;
+        self.history = game.history.clone();
+        // the marks depend on the check mode of this game
+        self.check_numbers();
+
+        game.duration
+
+    }
+
+    pub fn grid(&self) -> [[u8; BOARD_SIZE as usize]; BOARD_SIZE as usize] {
+        self.board
+    }
+
+    pub fn set_givens(&mut self, grid: &[[u8; BOARD_SIZE as usize]; BOARD_SIZE as usize]) {
+        self.clear();
+        for (x, col) in grid.iter().enumerate() {
+            for (y, &value) in col.iter().enumerate() {
+                self.board[x][y] = value.min(9);
+                self.editable[x][y] = value == 0;
+            }
+        }
+    }
+
+    pub fn set_puzzle(&mut self, grid: &[[u8; BOARD_SIZE as usize]; BOARD_SIZE as usize]) -> Result<(), String> {
+
+        self.set_givens(grid);
+
+        let result = match self.count_solutions(2) {
+            0 => Err("the puzzle has no solution".to_string()),
+            1 => Ok(()),
+            _ => Err("the puzzle has more than one solution".to_string()),
+        };
+        if result.is_err() {
+            self.clear();
+        }
+        result
+
+    }
+
+    pub fn export_text(&self) -> String {
+
+        // the puzzle and the current state in all formats, the text can be imported again
+        let mut result = "# sudoku export\n".to_string();
+        for (title, grid) in [("puzzle", self.givens().grid()), ("current state", self.grid())] {
+            for format in [Format::Line, Format::Sdk, Format::Ascii] {
+                result.push_str(&format!("\n# {}, {}\n", title, format.name()));
+                result.push_str(&write_puzzle(&grid, format));
+            }
+        }
+        result
+
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.progress() == 100 && self.unit_masks().is_some()
+    }
+
+    pub fn progress(&self) -> u32 {
+        let mut filled: u32 = 0;
+        for y in 0..BOARD_SIZE as usize {
+            for x in 0..BOARD_SIZE as usize {
+                if self.board[x][y] != 0 {
+                    filled += 1;
+                }
+            }
+        }
+        filled * 100 / (BOARD_SIZE * BOARD_SIZE) as u32
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_puzzles::*;
+
+    #[test]
+    fn count_solutions_of_unique_puzzle() {
+        assert_eq!(board(PUZZLE).count_solutions(2), 1);
+        assert_eq!(board(SOLUTION).count_solutions(2), 1);
+    }
+
+    #[test]
+    fn count_solutions_stops_at_limit() {
+        assert_eq!(SudokuBoard::default().count_solutions(3), 3);
+    }
+
+    #[test]
+    fn count_solutions_of_duplicate_numbers() {
+        let mut board = board(PUZZLE);
+        // a second 5 in the first row
+        board.set_field(2, 0, 5);
+        assert_eq!(board.count_solutions(2), 0);
+    }
+
+    #[test]
+    fn count_solutions_of_givens_ignores_entries() {
+        let mut board = board(PUZZLE);
+        // a wrong number of the player, the solution has a 4 there
+        board.editable[2][0] = true;
+        board.set_field(2, 0, 1);
+        assert_eq!(board.count_solutions(2), 0);
+        assert_eq!(board.givens().count_solutions(2), 1);
+    }
+
+    #[test]
+    fn fill_solution_replaces_wrong_numbers() {
+        let mut board = board(PUZZLE);
+        board.editable[2][0] = true;
+        board.set_field(2, 0, 1);
+        assert!(board.fill_solution());
+        assert_eq!(board.grid(), grid(SOLUTION));
+    }
+
+    #[test]
+    fn mistakes_need_a_check_mode() {
+        let mut board = board(PUZZLE);
+        board.editable[2][0] = true;
+        board.set_check_mode(CheckMode::Off);
+        board.enter_number(2, 0, 5, false);
+        assert_eq!(board.mistakes_made(), 0);
+        board.set_check_mode(CheckMode::Conflicts);
+        board.enter_number(2, 0, 3, false);
+        assert_eq!(board.mistakes_made(), 1);
+    }
+
+}
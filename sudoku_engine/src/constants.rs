@@ -0,0 +1,25 @@
+// dimensions of the board
+pub const BOARD_SIZE: i32 = 9;
+pub const FIELD_SIZE: i32 = 3;
+
+// version of the savegame format
+pub const SAVE_VERSION: u32 = 2;
+
+// the longest playing time of a savegame in seconds, about ten years
+pub const DURATION_MAX: u64 = 10 * 365 * 86_400;
+
+// number of givens left by the puzzle generator for each difficulty
+pub const CLUES_EASY: usize = 36;
+pub const CLUES_MOD: usize = 27;
+pub const CLUES_HARD: usize = 24;
+
+// score ranges of the logical grader for each difficulty
+pub const SCORE_EASY_MAX: u32 = 60;
+pub const SCORE_MOD_MAX: u32 = 200;
+pub const SCORE_UNSOLVED: u32 = 500;
+
+// puzzles the generator tries to hit the score range of a difficulty
+pub const GENERATOR_ATTEMPTS: u32 = 50;
+
+// seeds of new puzzles are below this number
+pub const SEED_MAX: u64 = 1_000_000_000;
@@ -0,0 +1,232 @@
+use crate::constants::BOARD_SIZE;
+
+const SIZE: usize = BOARD_SIZE as usize;
+const CELLS: usize = SIZE * SIZE;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Line,
+    Sdk,
+    Ascii,
+}
+
+impl Format {
+
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name.trim().to_lowercase().as_str() {
+            "line" | "txt" => Some(Format::Line),
+            "sdk" => Some(Format::Sdk),
+            "ascii" => Some(Format::Ascii),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Line => "line",
+            Format::Sdk => "sdk",
+            Format::Ascii => "ascii",
+        }
+    }
+
+}
+
+pub fn parse_puzzles(text: &str) -> Result<Vec<[[u8; SIZE]; SIZE]>, String> {
+
+    // reads every puzzle of a text: lines with 81 cells, the SadMan (.sdk)
+    // grid with 9 rows and the Simple Sudoku (.ss) grid with separators
+    let mut puzzles: Vec<[[u8; SIZE]; SIZE]> = Vec::new();
+    let mut rows: Vec<Vec<u8>> = Vec::new();
+    let mut skip: bool = false;
+
+    for (number, line) in text.lines().enumerate() {
+
+        let l = line.trim();
+
+        // sdk files may have sections, only the puzzle is read and not the state
+        if l.starts_with('[') {
+            skip = !l.eq_ignore_ascii_case("[puzzle]");
+            continue;
+        }
+
+        // comments, sdk headers like "#A author" and empty lines
+        if skip || l.is_empty() || l.starts_with('#') {
+            continue;
+        }
+
+        // separators of the ss grid, like "*-----------*", "|---+---+---|" or "-----------"
+        if l.chars().all(|c| c == '-' || c == '+' || c == '|' || c == '*' || c == ' ') {
+            continue;
+        }
+
+        // one puzzle per line, maybe followed by a rating or a comment
+        let first = l.split_whitespace().next().unwrap_or_default();
+        if first.len() == CELLS {
+            if !rows.is_empty() {
+                return Err(format!("line {}: incomplete grid before this puzzle", number + 1));
+            }
+            let cells = parse_cells(first).ok_or(format!("line {}: unknown cell in '{}'", number + 1, first))?;
+            puzzles.push(to_grid(&cells));
+            continue;
+        }
+
+        // a row of a grid, the ss format adds '|' between the blocks
+        let row: String = l.chars().filter(|c| *c != '|' && !c.is_whitespace()).collect();
+        if row.len() != SIZE {
+            return Err(format!("line {}: '{}' is no sudoku row", number + 1, l));
+        }
+        rows.push(parse_cells(&row).ok_or(format!("line {}: unknown cell in '{}'", number + 1, l))?);
+
+        if rows.len() == SIZE {
+            puzzles.push(to_grid(&rows.concat()));
+            rows.clear();
+        }
+
+    }
+
+    if !rows.is_empty() {
+        return Err(format!("the last grid has only {} rows", rows.len()));
+    }
+    if puzzles.is_empty() {
+        return Err("no puzzle found".to_string());
+    }
+
+    Ok(puzzles)
+
+}
+
+fn parse_cells(code: &str) -> Option<Vec<u8>> {
+    code.chars()
+        .map(|c| match c {
+            '1'..='9' => Some(c as u8 - b'0'),
+            '0' | '.' => Some(0),
+            _ => None,
+        })
+        .collect()
+}
+
+fn to_grid(cells: &[u8]) -> [[u8; SIZE]; SIZE] {
+    let mut grid = [[0; SIZE]; SIZE];
+    for (i, value) in cells.iter().enumerate() {
+        grid[i % SIZE][i / SIZE] = *value;
+    }
+    grid
+}
+
+pub fn write_puzzle(grid: &[[u8; SIZE]; SIZE], format: Format) -> String {
+
+    let cell = |x: usize, y: usize| if grid[x][y] == 0 { '.' } else { (grid[x][y] + b'0') as char };
+    let mut result = String::new();
+
+    match format {
+        Format::Line => {
+            for y in 0..SIZE {
+                (0..SIZE).for_each(|x| result.push(cell(x, y)));
+            }
+            result.push('\n');
+        }
+        Format::Sdk => {
+            for y in 0..SIZE {
+                (0..SIZE).for_each(|x| result.push(cell(x, y)));
+                result.push('\n');
+            }
+        }
+        Format::Ascii => {
+            // the grid can be read again like a Simple Sudoku file
+            let border = "+-------+-------+-------+\n";
+            for y in 0..SIZE {
+                if y % 3 == 0 {
+                    result.push_str(border);
+                }
+                for x in 0..SIZE {
+                    result.push_str(if x % 3 == 0 { "| " } else { "" });
+                    result.push(cell(x, y));
+                    result.push(' ');
+                }
+                result.push_str("|\n");
+            }
+            result.push_str(border);
+        }
+    }
+
+    result
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::constants::FIELD_SIZE;
+    use crate::test_puzzles::*;
+
+    // the rows of the puzzle, '.' is an empty cell
+    fn rows() -> Vec<String> {
+        PUZZLE.as_bytes().chunks(SIZE).map(|row| String::from_utf8_lossy(row).to_string()).collect()
+    }
+
+    #[test]
+    fn parse_lines() {
+        // zeros as empty cells, a rating after the puzzle and comments
+        let text = format!("# two puzzles\n{} 2.3\n\n{}\n", PUZZLE.replace('.', "0"), PUZZLE);
+        assert_eq!(parse_puzzles(&text).unwrap(), vec![grid(PUZZLE), grid(PUZZLE)]);
+    }
+
+    #[test]
+    fn parse_sdk_file() {
+        // the state of the game isn't a second puzzle
+        let text = format!("#A author\n#D a puzzle\n[Puzzle]\n{}\n[State]\n{}\n", rows().join("\n"), "123456789\n".repeat(SIZE));
+        assert_eq!(parse_puzzles(&text).unwrap(), vec![grid(PUZZLE)]);
+    }
+
+    #[test]
+    fn parse_ss_file() {
+        let mut text = String::new();
+        for (i, row) in rows().iter().enumerate() {
+            if i > 0 && i % FIELD_SIZE as usize == 0 {
+                text.push_str("---+---+---\n");
+            }
+            text.push_str(&format!("{}|{}|{}\n", &row[0..3], &row[3..6], &row[6..9]));
+        }
+        assert_eq!(parse_puzzles(&text).unwrap(), vec![grid(PUZZLE)]);
+    }
+
+    #[test]
+    fn parse_ss_file_with_border() {
+        // the puzzle as Simple Sudoku saves it
+        let text = "\
+*-----------*
+|53.|.7.|...|
+|6..|195|...|
+|.98|...|.6.|
+|---+---+---|
+|8..|.6.|..3|
+|4..|8.3|..1|
+|7..|.2.|..6|
+|---+---+---|
+|.6.|...|28.|
+|...|419|..5|
+|...|.8.|.79|
+*-----------*
+";
+        assert_eq!(parse_puzzles(text).unwrap(), vec![grid(PUZZLE)]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(parse_puzzles("").is_err());
+        assert!(parse_puzzles("# nothing\n").is_err());
+        assert!(parse_puzzles(&PUZZLE.replace('7', "x")).is_err());
+        // a grid with only two rows, and a row which is too short
+        assert!(parse_puzzles("53..7....\n6..195...\n").is_err());
+        assert!(parse_puzzles("53..7....\n6..195..\n").is_err());
+    }
+
+    #[test]
+    fn parse_written_formats() {
+        for format in [Format::Line, Format::Sdk, Format::Ascii] {
+            assert_eq!(parse_puzzles(&write_puzzle(&grid(PUZZLE), format)).unwrap(), vec![grid(PUZZLE)], "{}", format.name());
+        }
+    }
+
+}
@@ -0,0 +1,161 @@
+use crate::constants::BOARD_SIZE;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CellState {
+    pub value: u8,
+    pub notes: u16,
+    pub valid: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Change {
+    pub x: usize,
+    pub y: usize,
+    pub before: CellState,
+    pub after: CellState,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    moves: Vec<Vec<Change>>,
+    position: usize,
+}
+
+impl History {
+
+    pub fn push(&mut self, changes: Vec<Change>) {
+        if changes.is_empty() {
+            return;
+        }
+        // a new move drops all undone moves
+        self.moves.truncate(self.position);
+        self.moves.push(changes);
+        self.position = self.moves.len();
+    }
+
+    pub fn undo(&mut self) -> Option<&Vec<Change>> {
+        if self.position == 0 {
+            return None;
+        }
+        self.position -= 1;
+        self.moves.get(self.position)
+    }
+
+    pub fn redo(&mut self) -> Option<&Vec<Change>> {
+        if self.position >= self.moves.len() {
+            return None;
+        }
+        self.position += 1;
+        self.moves.get(self.position - 1)
+    }
+
+    pub fn clear(&mut self) {
+        self.moves.clear();
+        self.position = 0;
+    }
+
+    pub fn encode(&self) -> String {
+        // position, then every move with its changes as fixed width hex groups
+        let mut result = self.position.to_string();
+        for changes in self.moves.iter() {
+            result.push(';');
+            for c in changes.iter() {
+                result.push_str(&format!("{}{}{}{}", c.x, c.y, encode_cell(&c.before), encode_cell(&c.after)));
+            }
+        }
+        result
+    }
+
+    pub fn decode(code: &str) -> Option<History> {
+        let mut parts = code.split(';');
+        let position = parts.next()?.parse::<usize>().ok()?;
+        let mut moves: Vec<Vec<Change>> = Vec::new();
+        for part in parts {
+            if part.is_empty() || part.len() % 12 != 0 || !part.is_ascii() {
+                return None;
+            }
+            let mut changes: Vec<Change> = Vec::new();
+            for i in (0..part.len()).step_by(12) {
+                let c = &part[i..i + 12];
+                let change = Change {
+                    x: c[0..1].parse::<usize>().ok()?,
+                    y: c[1..2].parse::<usize>().ok()?,
+                    before: decode_cell(&c[2..7])?,
+                    after: decode_cell(&c[7..12])?,
+                };
+                if change.x >= BOARD_SIZE as usize || change.y >= BOARD_SIZE as usize {
+                    return None;
+                }
+                changes.push(change);
+            }
+            moves.push(changes);
+        }
+        if position > moves.len() {
+            return None;
+        }
+        Some(History { moves, position })
+    }
+
+}
+
+fn encode_cell(cell: &CellState) -> String {
+    format!("{}{:03x}{}", cell.value, cell.notes, cell.valid as u8)
+}
+
+fn decode_cell(code: &str) -> Option<CellState> {
+    Some(CellState {
+        value: code[0..1].parse::<u8>().ok()?,
+        notes: u16::from_str_radix(&code[1..4], 16).ok()?,
+        valid: &code[4..5] == "1",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn change(x: usize, y: usize, value: u8, notes: u16) -> Change {
+        Change {
+            x,
+            y,
+            before: CellState { value: 0, notes, valid: false },
+            after: CellState { value, notes: 0, valid: true },
+        }
+    }
+
+    #[test]
+    fn encode_decode() {
+        let mut history = History::default();
+        history.push(vec![change(0, 0, 5, 0)]);
+        history.push(vec![change(8, 2, 9, 0x3fe), change(4, 7, 1, 0x002)]);
+        history.push(vec![change(3, 3, 7, 0x080)]);
+        history.undo();
+
+        let code = history.encode();
+        let mut decoded = History::decode(&code).unwrap();
+        assert_eq!(decoded.encode(), code);
+        assert_eq!(decoded.redo(), Some(&vec![change(3, 3, 7, 0x080)]));
+        assert_eq!(decoded.redo(), None);
+        decoded.undo();
+        assert_eq!(decoded.undo(), Some(&vec![change(8, 2, 9, 0x3fe), change(4, 7, 1, 0x002)]));
+    }
+
+    #[test]
+    fn decode_empty() {
+        let history = History::decode("0").unwrap();
+        assert_eq!(history.encode(), "0");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        // the position is behind the last move
+        assert!(History::decode("2;000000500001").is_none());
+        // a change is cut off
+        assert!(History::decode("1;00000050000").is_none());
+        // a cell outside of the board
+        assert!(History::decode("1;900000500001").is_none());
+        assert!(History::decode("").is_none());
+    }
+
+}
@@ -0,0 +1,13 @@
+// SUDOKU engine
+// the board model, validation, solver, generator and file formats of the sudoku game,
+// without any GUI, so it can be used by other tools too
+
+pub mod board;
+pub mod constants;
+pub mod formats;
+pub mod history;
+pub mod logic;
+pub mod rng;
+pub mod savegame;
+#[cfg(test)]
+mod test_puzzles;
@@ -0,0 +1,933 @@
+use crate::board::{Symmetry, SudokuBoard};
+use crate::constants::*;
+use crate::rng::Rng;
+
+const SIZE: usize = BOARD_SIZE as usize;
+const BLOCK: usize = FIELD_SIZE as usize;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Technique {
+    NakedSingle,
+    HiddenSingle,
+    NakedPair,
+    HiddenPair,
+    NakedTriple,
+    HiddenTriple,
+    Pointing,
+    BoxLineReduction,
+    XWing,
+    Swordfish,
+    XYWing,
+    Chain,
+}
+
+impl Technique {
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Technique::NakedSingle => "Naked single",
+            Technique::HiddenSingle => "Hidden single",
+            Technique::NakedPair => "Naked pair",
+            Technique::HiddenPair => "Hidden pair",
+            Technique::NakedTriple => "Naked triple",
+            Technique::HiddenTriple => "Hidden triple",
+            Technique::Pointing => "Pointing",
+            Technique::BoxLineReduction => "Box/line reduction",
+            Technique::XWing => "X-Wing",
+            Technique::Swordfish => "Swordfish",
+            Technique::XYWing => "XY-Wing",
+            Technique::Chain => "Chain",
+        }
+    }
+
+    pub fn score(&self) -> u32 {
+        match self {
+            Technique::NakedSingle => 1,
+            Technique::HiddenSingle => 1,
+            Technique::NakedPair => 15,
+            Technique::HiddenPair => 20,
+            Technique::NakedTriple => 25,
+            Technique::HiddenTriple => 30,
+            Technique::Pointing => 10,
+            Technique::BoxLineReduction => 10,
+            Technique::XWing => 40,
+            Technique::Swordfish => 60,
+            Technique::XYWing => 50,
+            Technique::Chain => 80,
+        }
+    }
+
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Moderate,
+    Hard,
+}
+
+impl Difficulty {
+
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        match name.trim().to_lowercase().as_str() {
+            "easy" => Some(Difficulty::Easy),
+            "moderate" => Some(Difficulty::Moderate),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Moderate => "Moderate",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    pub fn letter(&self) -> char {
+        match self {
+            Difficulty::Easy => 'E',
+            Difficulty::Moderate => 'M',
+            Difficulty::Hard => 'H',
+        }
+    }
+
+    pub fn from_score(score: u32) -> Difficulty {
+        if score <= SCORE_EASY_MAX {
+            Difficulty::Easy
+        } else if score <= SCORE_MOD_MAX {
+            Difficulty::Moderate
+        } else {
+            Difficulty::Hard
+        }
+    }
+
+    pub fn clues(&self) -> usize {
+        match self {
+            Difficulty::Easy => CLUES_EASY,
+            Difficulty::Moderate => CLUES_MOD,
+            Difficulty::Hard => CLUES_HARD,
+        }
+    }
+
+    pub fn symmetry(&self) -> Symmetry {
+        match self {
+            Difficulty::Easy => Symmetry::Mirror,
+            Difficulty::Moderate => Symmetry::Rotational,
+            Difficulty::Hard => Symmetry::None,
+        }
+    }
+
+    pub fn score_range(&self) -> (u32, u32) {
+        match self {
+            Difficulty::Easy => (0, SCORE_EASY_MAX),
+            Difficulty::Moderate => (SCORE_EASY_MAX + 1, SCORE_MOD_MAX),
+            Difficulty::Hard => (SCORE_MOD_MAX + 1, u32::MAX),
+        }
+    }
+
+}
+
+#[derive(Clone, Debug)]
+pub struct Step {
+    pub technique: Technique,
+    pub placements: Vec<(usize, usize, u8)>,
+    pub eliminations: Vec<(usize, usize, u8)>,
+    pub cells: Vec<(usize, usize)>,
+    pub description: String,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Grade {
+    pub hardest: Option<Technique>,
+    pub score: u32,
+    pub solved: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Unit {
+    Row(usize),
+    Col(usize),
+    Block(usize),
+}
+
+impl Unit {
+
+    fn all() -> Vec<Unit> {
+        let mut units: Vec<Unit> = Vec::new();
+        for i in 0..SIZE { units.push(Unit::Row(i)); }
+        for i in 0..SIZE { units.push(Unit::Col(i)); }
+        for i in 0..SIZE { units.push(Unit::Block(i)); }
+        units
+    }
+
+    fn cells(&self) -> Vec<(usize, usize)> {
+        match *self {
+            Unit::Row(y) => (0..SIZE).map(|x| (x, y)).collect(),
+            Unit::Col(x) => (0..SIZE).map(|y| (x, y)).collect(),
+            Unit::Block(b) => {
+                let (bx, by) = ((b % BLOCK) * BLOCK, (b / BLOCK) * BLOCK);
+                (0..SIZE).map(|i| (bx + i % BLOCK, by + i / BLOCK)).collect()
+            }
+        }
+    }
+
+    fn name(&self) -> String {
+        match *self {
+            Unit::Row(y) => format!("row {}", y + 1),
+            Unit::Col(x) => format!("column {}", x + 1),
+            Unit::Block(b) => format!("block {}", b + 1),
+        }
+    }
+
+}
+
+fn block_of(x: usize, y: usize) -> usize {
+    (y / BLOCK) * BLOCK + x / BLOCK
+}
+
+fn sees(a: (usize, usize), b: (usize, usize)) -> bool {
+    a != b && (a.0 == b.0 || a.1 == b.1 || block_of(a.0, a.1) == block_of(b.0, b.1))
+}
+
+fn cell_name(x: usize, y: usize) -> String {
+    format!("row {}, column {}", y + 1, x + 1)
+}
+
+fn digits_of(mask: u16) -> Vec<u8> {
+    (1..=SIZE as u8).filter(|d| mask & (1 << d) != 0).collect()
+}
+
+fn digits_name(mask: u16) -> String {
+    digits_of(mask).iter().map(|d| d.to_string()).collect::<Vec<String>>().join("/")
+}
+
+fn combinations(items: &[usize], n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+    let mut result: Vec<Vec<usize>> = Vec::new();
+    for i in 0..items.len() {
+        for mut rest in combinations(&items[i + 1..], n - 1) {
+            rest.insert(0, items[i]);
+            result.push(rest);
+        }
+    }
+    result
+}
+
+#[derive(Clone, Debug)]
+pub struct LogicSolver {
+    values: [[u8; SIZE]; SIZE],
+    cands: [[u16; SIZE]; SIZE],
+}
+
+impl LogicSolver {
+
+    pub fn new(board: &SudokuBoard) -> LogicSolver {
+        let mut solver = LogicSolver {
+            values: [[0; SIZE]; SIZE],
+            cands: [[0; SIZE]; SIZE],
+        };
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let value = board.get_field(x, y);
+                solver.values[x][y] = value;
+                if value == 0 {
+                    solver.cands[x][y] = board.candidates(x, y);
+                }
+            }
+        }
+        solver
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.values.iter().all(|col| col.iter().all(|&v| v != 0))
+    }
+
+    pub fn apply(&mut self, step: &Step) {
+        for &(x, y, value) in step.placements.iter() {
+            self.place(x, y, value);
+        }
+        for &(x, y, value) in step.eliminations.iter() {
+            self.cands[x][y] &= !(1 << value);
+        }
+    }
+
+    fn place(&mut self, x: usize, y: usize, value: u8) {
+        self.values[x][y] = value;
+        self.cands[x][y] = 0;
+        for py in 0..SIZE {
+            for px in 0..SIZE {
+                if sees((x, y), (px, py)) {
+                    self.cands[px][py] &= !(1 << value);
+                }
+            }
+        }
+    }
+
+    fn positions(&self, unit: Unit, value: u8) -> Vec<(usize, usize)> {
+        unit.cells().into_iter().filter(|&(x, y)| self.cands[x][y] & (1 << value) != 0).collect()
+    }
+
+    pub fn next_step(&self) -> Option<Step> {
+        self.naked_single()
+            .or_else(|| self.hidden_single())
+            .or_else(|| self.naked_subset(2))
+            .or_else(|| self.hidden_subset(2))
+            .or_else(|| self.naked_subset(3))
+            .or_else(|| self.hidden_subset(3))
+            .or_else(|| self.pointing())
+            .or_else(|| self.box_line_reduction())
+            .or_else(|| self.fish(2))
+            .or_else(|| self.fish(3))
+            .or_else(|| self.xy_wing())
+            .or_else(|| self.chain())
+    }
+
+    fn naked_single(&self) -> Option<Step> {
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                if self.values[x][y] == 0 && self.cands[x][y].count_ones() == 1 {
+                    let value = self.cands[x][y].trailing_zeros() as u8;
+                    return Some(Step {
+                        technique: Technique::NakedSingle,
+                        placements: vec![(x, y, value)],
+                        eliminations: Vec::new(),
+                        cells: vec![(x, y)],
+                        description: format!("Naked single: {} in {}", value, cell_name(x, y)),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn hidden_single(&self) -> Option<Step> {
+        for unit in Unit::all() {
+            for value in 1..=SIZE as u8 {
+                let pos = self.positions(unit, value);
+                if pos.len() == 1 {
+                    let (x, y) = pos[0];
+                    return Some(Step {
+                        technique: Technique::HiddenSingle,
+                        placements: vec![(x, y, value)],
+                        eliminations: Vec::new(),
+                        cells: unit.cells(),
+                        description: format!("Hidden single: {} in {}", value, unit.name()),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn naked_subset(&self, n: usize) -> Option<Step> {
+        for unit in Unit::all() {
+            let cells = unit.cells();
+            let empty: Vec<usize> = (0..SIZE)
+                .filter(|&i| self.values[cells[i].0][cells[i].1] == 0)
+                .collect();
+            for combo in combinations(&empty, n) {
+                let mask = combo.iter().fold(0u16, |m, &i| m | self.cands[cells[i].0][cells[i].1]);
+                if mask.count_ones() as usize != n {
+                    continue;
+                }
+                let mut eliminations: Vec<(usize, usize, u8)> = Vec::new();
+                for &i in empty.iter().filter(|i| !combo.contains(i)) {
+                    let (x, y) = cells[i];
+                    for value in digits_of(self.cands[x][y] & mask) {
+                        eliminations.push((x, y, value));
+                    }
+                }
+                if !eliminations.is_empty() {
+                    let technique = if n == 2 { Technique::NakedPair } else { Technique::NakedTriple };
+                    return Some(Step {
+                        technique,
+                        placements: Vec::new(),
+                        eliminations,
+                        cells: combo.iter().map(|&i| cells[i]).collect(),
+                        description: format!("{}: {} in {}", technique.name(), digits_name(mask), unit.name()),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn hidden_subset(&self, n: usize) -> Option<Step> {
+        for unit in Unit::all() {
+            let cells = unit.cells();
+            let open: Vec<usize> = (1..=SIZE)
+                .filter(|&d| !self.positions(unit, d as u8).is_empty())
+                .collect();
+            for combo in combinations(&open, n) {
+                let mut places: Vec<(usize, usize)> = Vec::new();
+                for &d in combo.iter() {
+                    for p in self.positions(unit, d as u8) {
+                        if !places.contains(&p) {
+                            places.push(p);
+                        }
+                    }
+                }
+                if places.len() != n {
+                    continue;
+                }
+                let mask = combo.iter().fold(0u16, |m, &d| m | (1 << d));
+                let mut eliminations: Vec<(usize, usize, u8)> = Vec::new();
+                for &(x, y) in places.iter() {
+                    for value in digits_of(self.cands[x][y] & !mask) {
+                        eliminations.push((x, y, value));
+                    }
+                }
+                if !eliminations.is_empty() {
+                    let technique = if n == 2 { Technique::HiddenPair } else { Technique::HiddenTriple };
+                    return Some(Step {
+                        technique,
+                        placements: Vec::new(),
+                        eliminations,
+                        cells: cells.into_iter().filter(|p| places.contains(p)).collect(),
+                        description: format!("{}: {} in {}", technique.name(), digits_name(mask), unit.name()),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn pointing(&self) -> Option<Step> {
+        for b in 0..SIZE {
+            let block = Unit::Block(b);
+            for value in 1..=SIZE as u8 {
+                let pos = self.positions(block, value);
+                if pos.len() < 2 {
+                    continue;
+                }
+                let line = if pos.iter().all(|p| p.1 == pos[0].1) {
+                    Unit::Row(pos[0].1)
+                } else if pos.iter().all(|p| p.0 == pos[0].0) {
+                    Unit::Col(pos[0].0)
+                } else {
+                    continue;
+                };
+                let eliminations: Vec<(usize, usize, u8)> = self.positions(line, value)
+                    .into_iter()
+                    .filter(|&(x, y)| block_of(x, y) != b)
+                    .map(|(x, y)| (x, y, value))
+                    .collect();
+                if !eliminations.is_empty() {
+                    return Some(Step {
+                        technique: Technique::Pointing,
+                        placements: Vec::new(),
+                        eliminations,
+                        cells: pos,
+                        description: format!("Pointing: {} in {} is locked to {}", value, block.name(), line.name()),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn box_line_reduction(&self) -> Option<Step> {
+        for unit in Unit::all() {
+            if let Unit::Block(_) = unit {
+                continue;
+            }
+            for value in 1..=SIZE as u8 {
+                let pos = self.positions(unit, value);
+                if pos.len() < 2 {
+                    continue;
+                }
+                let b = block_of(pos[0].0, pos[0].1);
+                if !pos.iter().all(|&(x, y)| block_of(x, y) == b) {
+                    continue;
+                }
+                let eliminations: Vec<(usize, usize, u8)> = self.positions(Unit::Block(b), value)
+                    .into_iter()
+                    .filter(|p| !pos.contains(p))
+                    .map(|(x, y)| (x, y, value))
+                    .collect();
+                if !eliminations.is_empty() {
+                    return Some(Step {
+                        technique: Technique::BoxLineReduction,
+                        placements: Vec::new(),
+                        eliminations,
+                        cells: pos,
+                        description: format!("Box/line reduction: {} in {} is locked to {}", value, unit.name(), Unit::Block(b).name()),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn fish(&self, n: usize) -> Option<Step> {
+        let technique = if n == 2 { Technique::XWing } else { Technique::Swordfish };
+        for value in 1..=SIZE as u8 {
+            for by_rows in [true, false] {
+                let line = |i: usize| if by_rows { Unit::Row(i) } else { Unit::Col(i) };
+                let cover = |i: usize| if by_rows { Unit::Col(i) } else { Unit::Row(i) };
+                // index of the cover line for a cell
+                let cross = |p: (usize, usize)| if by_rows { p.0 } else { p.1 };
+                let bases: Vec<usize> = (0..SIZE)
+                    .filter(|&i| (2..=n).contains(&self.positions(line(i), value).len()))
+                    .collect();
+                for combo in combinations(&bases, n) {
+                    let mut covers: Vec<usize> = Vec::new();
+                    let mut cells: Vec<(usize, usize)> = Vec::new();
+                    for &i in combo.iter() {
+                        for p in self.positions(line(i), value) {
+                            if !covers.contains(&cross(p)) {
+                                covers.push(cross(p));
+                            }
+                            cells.push(p);
+                        }
+                    }
+                    if covers.len() != n {
+                        continue;
+                    }
+                    let mut eliminations: Vec<(usize, usize, u8)> = Vec::new();
+                    for &c in covers.iter() {
+                        for p in self.positions(cover(c), value) {
+                            if !cells.contains(&p) {
+                                eliminations.push((p.0, p.1, value));
+                            }
+                        }
+                    }
+                    if !eliminations.is_empty() {
+                        let names: Vec<String> = combo.iter().map(|&i| (i + 1).to_string()).collect();
+                        return Some(Step {
+                            technique,
+                            placements: Vec::new(),
+                            eliminations,
+                            cells,
+                            description: format!("{}: {} in {} {}", technique.name(), value,
+                                                 if by_rows { "rows" } else { "columns" }, names.join(", ")),
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn xy_wing(&self) -> Option<Step> {
+        let mut bivalue: Vec<(usize, usize)> = Vec::new();
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                if self.cands[x][y].count_ones() == 2 {
+                    bivalue.push((x, y));
+                }
+            }
+        }
+        for &pivot in bivalue.iter() {
+            let pm = self.cands[pivot.0][pivot.1];
+            for &a in bivalue.iter().filter(|&&a| sees(pivot, a)) {
+                let am = self.cands[a.0][a.1];
+                if am == pm || (am & pm).count_ones() != 1 {
+                    continue;
+                }
+                for &b in bivalue.iter().filter(|&&b| sees(pivot, b) && b != a) {
+                    let bm = self.cands[b.0][b.1];
+                    // both pincers share the third digit and cover the pivot together
+                    let z = am & bm & !pm;
+                    if z.count_ones() != 1 || (am | bm) & pm != pm || bm == pm {
+                        continue;
+                    }
+                    let value = z.trailing_zeros() as u8;
+                    let mut eliminations: Vec<(usize, usize, u8)> = Vec::new();
+                    for y in 0..SIZE {
+                        for x in 0..SIZE {
+                            if self.cands[x][y] & z != 0 && sees(a, (x, y)) && sees(b, (x, y)) {
+                                eliminations.push((x, y, value));
+                            }
+                        }
+                    }
+                    if !eliminations.is_empty() {
+                        return Some(Step {
+                            technique: Technique::XYWing,
+                            placements: Vec::new(),
+                            eliminations,
+                            cells: vec![pivot, a, b],
+                            description: format!("XY-Wing: pivot in {} removes {}", cell_name(pivot.0, pivot.1), value),
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn chain(&self) -> Option<Step> {
+        for value in 1..=SIZE as u8 {
+
+            // collect all strong links, units with exactly two places for the digit
+            let mut links: Vec<((usize, usize), (usize, usize))> = Vec::new();
+            for unit in Unit::all() {
+                let pos = self.positions(unit, value);
+                if pos.len() == 2 && !links.contains(&(pos[0], pos[1])) {
+                    links.push((pos[0], pos[1]));
+                }
+            }
+
+            // colour every chain of strong links with two alternating colours
+            let mut colour: Vec<((usize, usize), usize, bool)> = Vec::new();
+            let mut chain_id: usize = 0;
+            for &(start, _) in links.iter() {
+                if colour.iter().any(|c| c.0 == start) {
+                    continue;
+                }
+                let mut stack: Vec<((usize, usize), bool)> = vec![(start, true)];
+                while let Some((cell, on)) = stack.pop() {
+                    if colour.iter().any(|c| c.0 == cell) {
+                        continue;
+                    }
+                    colour.push((cell, chain_id, on));
+                    for &(a, b) in links.iter() {
+                        if a == cell { stack.push((b, !on)); }
+                        if b == cell { stack.push((a, !on)); }
+                    }
+                }
+                chain_id += 1;
+            }
+
+            for id in 0..chain_id {
+                let members: Vec<((usize, usize), bool)> = colour.iter()
+                    .filter(|c| c.1 == id)
+                    .map(|c| (c.0, c.2))
+                    .collect();
+                let cells: Vec<(usize, usize)> = members.iter().map(|m| m.0).collect();
+
+                // two cells of the same colour see each other, so this colour is false
+                for on in [true, false] {
+                    let same: Vec<(usize, usize)> = members.iter().filter(|m| m.1 == on).map(|m| m.0).collect();
+                    let clash = same.iter().any(|&a| same.iter().any(|&b| sees(a, b)));
+                    if clash {
+                        return Some(Step {
+                            technique: Technique::Chain,
+                            placements: Vec::new(),
+                            eliminations: same.iter().map(|&(x, y)| (x, y, value)).collect(),
+                            cells,
+                            description: format!("Chain: colouring of {} contradicts itself", value),
+                        });
+                    }
+                }
+
+                // a cell outside the chain which sees both colours can't hold the digit
+                let mut eliminations: Vec<(usize, usize, u8)> = Vec::new();
+                for y in 0..SIZE {
+                    for x in 0..SIZE {
+                        if self.cands[x][y] & (1 << value) == 0 || cells.contains(&(x, y)) {
+                            continue;
+                        }
+                        let sees_on = members.iter().any(|m| m.1 && sees(m.0, (x, y)));
+                        let sees_off = members.iter().any(|m| !m.1 && sees(m.0, (x, y)));
+                        if sees_on && sees_off {
+                            eliminations.push((x, y, value));
+                        }
+                    }
+                }
+                if !eliminations.is_empty() {
+                    return Some(Step {
+                        technique: Technique::Chain,
+                        placements: Vec::new(),
+                        eliminations,
+                        cells,
+                        description: format!("Chain: {} is removed by both ends of a chain", value),
+                    });
+                }
+            }
+
+        }
+        None
+    }
+
+}
+
+pub fn grade(board: &SudokuBoard) -> Grade {
+
+    let mut solver = LogicSolver::new(&board.givens());
+    let mut grade = Grade {
+        hardest: None,
+        score: 0,
+        solved: false,
+    };
+
+    while !solver.is_solved() {
+        let Some(step) = solver.next_step() else {
+            break;
+        };
+        grade.score += step.technique.score();
+        if grade.hardest.is_none_or(|t| t.score() < step.technique.score()) {
+            grade.hardest = Some(step.technique);
+        }
+        solver.apply(&step);
+    }
+
+    // the puzzle needs guessing, rate it above every technique
+    grade.solved = solver.is_solved();
+    if !grade.solved {
+        grade.score += SCORE_UNSOLVED;
+    }
+
+    grade
+
+}
+
+pub fn hint(board: &SudokuBoard) -> Result<Step, String> {
+
+    let Some(solution) = board.solution() else {
+        return Err("This puzzle has no solution".to_string());
+    };
+
+    // a wrong number of the player leads every deduction astray
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let value = board.get_field(x, y);
+            if board.is_editable(x, y) && value != 0 && value != solution.get_field(x, y) {
+                return Err(format!("The {} in {} is wrong", value, cell_name(x, y)));
+            }
+        }
+    }
+
+    // collect all eliminations until a number can be placed
+    let mut solver = LogicSolver::new(board);
+    if solver.is_solved() {
+        return Err("The board is already complete".to_string());
+    }
+    let mut result: Option<Step> = None;
+
+    while let Some(step) = solver.next_step() {
+        solver.apply(&step);
+        let placed = !step.placements.is_empty();
+        result = Some(match result {
+            None => step,
+            Some(mut prev) => {
+                if step.technique.score() > prev.technique.score() {
+                    prev.technique = step.technique;
+                }
+                prev.placements = step.placements;
+                prev.eliminations.extend(step.eliminations);
+                for cell in step.cells {
+                    if !prev.cells.contains(&cell) {
+                        prev.cells.push(cell);
+                    }
+                }
+                prev.description = format!("{}; {}", prev.description, step.description);
+                prev
+            }
+        });
+        if placed {
+            break;
+        }
+    }
+
+    match result {
+        Some(step) if !step.placements.is_empty() => Ok(step),
+        _ => Err("No logical step found, only guessing helps now".to_string()),
+    }
+
+}
+
+pub fn generate(board: &mut SudokuBoard, difficulty: Difficulty, seed: u64) -> Grade {
+
+    // the same seed and difficulty create always the same puzzle
+    let mut rng = Rng::new(seed);
+    let (min, max) = difficulty.score_range();
+    let mut best: Option<(SudokuBoard, Grade)> = None;
+
+    for _ in 0..GENERATOR_ATTEMPTS {
+
+        board.generate(difficulty.clues(), difficulty.symmetry(), &mut rng);
+        let grade = grade(board);
+
+        // the hints of the game need a puzzle which its techniques can solve
+        if grade.solved && grade.score >= min && grade.score <= max {
+            board.set_difficulty(Some(difficulty));
+            board.set_seed(Some(seed));
+            return grade;
+        }
+
+        // keep the puzzle closest to the wanted range, a solvable one wins at the same distance
+        let distance = |g: &Grade| (if g.score < min { min - g.score } else { g.score.saturating_sub(max) }, !g.solved);
+        if best.as_ref().is_none_or(|(_, g)| distance(&grade) < distance(g)) {
+            best = Some((board.clone(), grade));
+        }
+
+    }
+
+    let (best_board, best_grade) = best.unwrap();
+    *board = best_board;
+    board.set_difficulty(Some(difficulty));
+    board.set_seed(Some(seed));
+    best_grade
+
+}
+
+pub fn new_seed(rng: &mut Rng) -> u64 {
+    // short enough to type it in
+    rng.next_u64() % SEED_MAX
+}
+
+pub fn seed_code(difficulty: Difficulty, seed: u64) -> String {
+    format!("{}-{}", difficulty.letter(), seed)
+}
+
+pub fn parse_seed_code(code: &str) -> Option<(Difficulty, u64)> {
+    // the letter of the difficulty and the seed, like "M-123456"
+    let code = code.trim().to_uppercase();
+    let mut chars = code.chars();
+    let difficulty = match chars.next()? {
+        'E' => Difficulty::Easy,
+        'M' => Difficulty::Moderate,
+        'H' => Difficulty::Hard,
+        _ => return None,
+    };
+    let seed = chars.as_str().trim_start_matches('-').parse::<u64>().ok()?;
+    Some((difficulty, seed))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_puzzles::*;
+
+    // puzzles which need the technique on the way to their solution
+    const TECHNIQUES: [(Technique, &str); 10] = [
+        (Technique::NakedPair, ".5.29...1..7....5...4.....93..4...8..4..6.2....5........392....79...5..4..8.3...."),
+        (Technique::HiddenPair, ".9.36..1.....81..........765...7.6.3.2.4...879....3.....6..9...74....2....8.....1"),
+        (Technique::NakedTriple, "37..86.1..........6.25....4...4..79.....193.......8...73....6........4.89.8....3."),
+        (Technique::HiddenTriple, "...5.39.7......4...6.8...3.4....9...9..6.8...5.....7.8..1....6.3.841...2....3...4"),
+        (Technique::Pointing, ".6....71.4.....5.........821....8.2....9.3..73.41.7...6..8.5...2.7.......3..9..6."),
+        (Technique::BoxLineReduction, ".6....4..7....365.3...54.....28......7...59.2........6.5.2....9....1..3.936.....7"),
+        (Technique::XWing, "6.2...7..........14..2..6.9..39..58.7..8..9.4.5...3...37.64.......7..........5.9."),
+        (Technique::Swordfish, ".5..1..2..7...21........4.91..4..6...2.......3.5....8....3......19..72....7.28..5"),
+        (Technique::XYWing, ".6......83.8.79.......1...2....2.1.75.......9.1.3...8..7.....2...12..6...394....."),
+        (Technique::Chain, ".9..6...5.81....6.....98...52....31...8..2..9....8..4...91..8.......642...5.....7"),
+    ];
+
+    // solvable with techniques up to chains, with a score above the moderate range
+    const HARD: &str = "2..65.8.......16......8...2.6..72..9.574..........5.4..7.8.....5.....29...6.4....";
+
+    // the techniques get stuck, only guessing solves it
+    const GUESSING: &str = "3......7..679...4.....17....3.1..98.2...6......9.2..64.4.....25..2....3.....71...";
+
+    // all techniques used to solve the puzzle, every step has to agree with the solution
+    fn solve_steps(code: &str) -> Vec<Technique> {
+
+        let board = board(code);
+        let mut solution = board.clone();
+        assert!(solution.solve());
+
+        let mut solver = LogicSolver::new(&board);
+        let mut techniques: Vec<Technique> = Vec::new();
+
+        while let Some(step) = solver.next_step() {
+            for &(x, y, value) in step.placements.iter() {
+                assert_eq!(value, solution.get_field(x, y), "{}", step.description);
+            }
+            for &(x, y, value) in step.eliminations.iter() {
+                assert_ne!(value, solution.get_field(x, y), "{}", step.description);
+            }
+            techniques.push(step.technique);
+            solver.apply(&step);
+        }
+
+        assert!(solver.is_solved());
+        techniques
+
+    }
+
+    #[test]
+    fn techniques() {
+        for (technique, code) in TECHNIQUES.iter() {
+            assert!(solve_steps(code).contains(technique), "{}", technique.name());
+        }
+    }
+
+    #[test]
+    fn grade_easy() {
+        let grade = grade(&board(PUZZLE));
+        assert!(grade.solved);
+        assert!(grade.score <= SCORE_EASY_MAX);
+        assert!(grade.hardest.is_some_and(|t| t.score() == 1));
+    }
+
+    #[test]
+    fn grade_hard() {
+        let grade = grade(&board(HARD));
+        assert!(grade.solved);
+        assert!(grade.score > SCORE_MOD_MAX);
+        assert_eq!(grade.hardest, Some(Technique::Chain));
+    }
+
+    #[test]
+    fn grade_guessing() {
+        let grade = grade(&board(GUESSING));
+        assert!(!grade.solved);
+        assert!(grade.score >= SCORE_UNSOLVED);
+        assert!(board(GUESSING).has_unique_solution());
+    }
+
+    #[test]
+    fn hint_next_step() {
+        let step = hint(&board(PUZZLE)).unwrap();
+        let solution = board(SOLUTION);
+        assert!(!step.placements.is_empty());
+        for &(x, y, value) in step.placements.iter() {
+            assert_eq!(value, solution.get_field(x, y));
+        }
+    }
+
+    #[test]
+    fn hint_wrong_number() {
+        let mut board = SudokuBoard::default();
+        board.generate(CLUES_EASY, Symmetry::Mirror, &mut Rng::new(1));
+        let solution = board.solution().unwrap();
+        let (x, y) = (0..SIZE * SIZE)
+            .map(|i| (i % SIZE, i / SIZE))
+            .find(|&(x, y)| board.is_editable(x, y))
+            .unwrap();
+        board.set_field(x, y, solution.get_field(x, y) % 9 + 1);
+        assert_eq!(hint(&board).unwrap_err(), format!("The {} in {} is wrong", board.get_field(x, y), cell_name(x, y)));
+    }
+
+    #[test]
+    fn hint_complete_board() {
+        assert_eq!(hint(&board(SOLUTION)).unwrap_err(), "The board is already complete");
+    }
+
+
+    #[test]
+    fn generate_same_seed() {
+        let mut first = SudokuBoard::default();
+        let mut second = SudokuBoard::default();
+        generate(&mut first, Difficulty::Easy, 4711);
+        generate(&mut second, Difficulty::Easy, 4711);
+        assert_eq!(first.grid(), second.grid());
+        assert!(first.has_unique_solution());
+        assert_eq!(first.get_seed(), Some(4711));
+        assert_eq!(first.get_difficulty(), Some(Difficulty::Easy));
+    }
+
+    #[test]
+    fn generate_other_seed() {
+        let mut first = SudokuBoard::default();
+        let mut second = SudokuBoard::default();
+        generate(&mut first, Difficulty::Easy, 4711);
+        generate(&mut second, Difficulty::Easy, 4712);
+        assert_ne!(first.grid(), second.grid());
+    }
+
+    #[test]
+    fn seed_code_round_trip() {
+        let code = seed_code(Difficulty::Hard, 123456);
+        assert_eq!(parse_seed_code(&code), Some((Difficulty::Hard, 123456)));
+        assert_eq!(parse_seed_code("m123"), Some((Difficulty::Moderate, 123)));
+        assert_eq!(parse_seed_code("X-1"), None);
+    }
+
+}
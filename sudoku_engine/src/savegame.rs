@@ -0,0 +1,318 @@
+use std::fmt;
+
+use crate::constants::*;
+use crate::history::History;
+use crate::logic::Difficulty;
+
+const SIZE: usize = BOARD_SIZE as usize;
+const CELLS: usize = SIZE * SIZE;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SaveError {
+    Io(String),
+    Empty,
+    UnsupportedVersion(u32),
+    MissingField(&'static str),
+    InvalidField(&'static str),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "{}", err),
+            SaveError::Empty => write!(f, "the savegame is empty"),
+            SaveError::UnsupportedVersion(v) => write!(f, "the savegame format {} is newer than this game", v),
+            SaveError::MissingField(name) => write!(f, "the savegame has no '{}'", name),
+            SaveError::InvalidField(name) => write!(f, "the savegame has an invalid '{}'", name),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SaveGame {
+    pub version: u32,
+    pub givens: [[u8; SIZE]; SIZE],
+    pub values: [[u8; SIZE]; SIZE],
+    pub valid: [[bool; SIZE]; SIZE],
+    pub notes: [[u16; SIZE]; SIZE],
+    pub duration: u64,
+    pub hints: u32,
+    pub mistakes: u32,
+    pub difficulty: Option<Difficulty>,
+    pub seed: Option<u64>,
+    pub daily: bool,
+    pub history: History,
+}
+
+impl SaveGame {
+
+    pub fn parse(text: &str) -> Result<SaveGame, SaveError> {
+
+        let first = text.lines()
+            .map(|l| l.trim())
+            .find(|l| !l.is_empty() && !l.starts_with('#'))
+            .ok_or(SaveError::Empty)?;
+
+        // the first format was a single line with comma separated values
+        if !first.contains('=') {
+            return SaveGame::parse_v1(first);
+        }
+
+        let mut game = SaveGame::default();
+        let mut has_givens = false;
+        let mut has_values = false;
+
+        for line in text.lines() {
+
+            let l = line.trim();
+            if l.is_empty() || l.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = l.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+
+            match key.trim().to_lowercase().as_str() {
+                "format" => {
+                    game.version = value.parse::<u32>().map_err(|_| SaveError::InvalidField("format"))?;
+                    if game.version > SAVE_VERSION {
+                        return Err(SaveError::UnsupportedVersion(game.version));
+                    }
+                }
+                "givens" => {
+                    game.givens = parse_grid(value).ok_or(SaveError::InvalidField("givens"))?;
+                    has_givens = true;
+                }
+                "values" => {
+                    game.values = parse_grid(value).ok_or(SaveError::InvalidField("values"))?;
+                    has_values = true;
+                }
+                "valid" => game.valid = parse_flags(value).ok_or(SaveError::InvalidField("valid"))?,
+                "notes" => game.notes = parse_notes(value).ok_or(SaveError::InvalidField("notes"))?,
+                "duration" => game.duration = parse_duration(value).ok_or(SaveError::InvalidField("duration"))?,
+                "hints" => game.hints = value.parse::<u32>().map_err(|_| SaveError::InvalidField("hints"))?,
+                "mistakes" => game.mistakes = value.parse::<u32>().map_err(|_| SaveError::InvalidField("mistakes"))?,
+                "difficulty" => game.difficulty = Some(Difficulty::from_name(value).ok_or(SaveError::InvalidField("difficulty"))?),
+                "seed" => game.seed = Some(value.parse::<u64>().map_err(|_| SaveError::InvalidField("seed"))?),
+                "daily" => game.daily = value.parse::<bool>().map_err(|_| SaveError::InvalidField("daily"))?,
+                "history" => game.history = History::decode(value).ok_or(SaveError::InvalidField("history"))?,
+                // newer minor additions are skipped
+                _ => (),
+            }
+
+        }
+
+        if game.version == 0 {
+            return Err(SaveError::MissingField("format"));
+        }
+        if !has_givens {
+            return Err(SaveError::MissingField("givens"));
+        }
+        if !has_values {
+            return Err(SaveError::MissingField("values"));
+        }
+
+        game.check()?;
+        Ok(game)
+
+    }
+
+    fn parse_v1(line: &str) -> Result<SaveGame, SaveError> {
+
+        // the numbers and the playing time, nothing else
+        let (values, duration) = line.split_once(',').ok_or(SaveError::MissingField("duration"))?;
+
+        let mut game = SaveGame {
+            version: 1,
+            values: parse_grid(values).ok_or(SaveError::InvalidField("values"))?,
+            duration: parse_duration(duration).ok_or(SaveError::InvalidField("duration"))?,
+            ..Default::default()
+        };
+
+        // the first files don't know the givens, so all numbers become givens
+        game.givens = game.values;
+
+        Ok(game)
+
+    }
+
+    fn check(&self) -> Result<(), SaveError> {
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                if self.givens[x][y] != 0 && self.givens[x][y] != self.values[x][y] {
+                    return Err(SaveError::InvalidField("values"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn to_text(&self) -> String {
+
+        let mut result = "# sudoku savegame\n".to_string();
+        result.push_str(&format!("format={}\n", SAVE_VERSION));
+        result.push_str(&format!("givens={}\n", grid_string(&self.givens)));
+        result.push_str(&format!("values={}\n", grid_string(&self.values)));
+
+        let mut valid = String::new();
+        let mut notes = String::new();
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                valid.push(if self.valid[x][y] { '1' } else { '0' });
+                notes.push_str(&format!("{:03x}", self.notes[x][y]));
+            }
+        }
+        result.push_str(&format!("valid={}\n", valid));
+        result.push_str(&format!("notes={}\n", notes));
+
+        result.push_str(&format!("duration={}\n", self.duration));
+        result.push_str(&format!("hints={}\n", self.hints));
+        result.push_str(&format!("mistakes={}\n", self.mistakes));
+        if let Some(difficulty) = self.difficulty {
+            result.push_str(&format!("difficulty={}\n", difficulty.name()));
+        }
+        if let Some(seed) = self.seed {
+            result.push_str(&format!("seed={}\n", seed));
+        }
+        if self.daily {
+            result.push_str("daily=true\n");
+        }
+        result.push_str(&format!("history={}\n", self.history.encode()));
+
+        result
+
+    }
+
+}
+
+fn parse_grid(code: &str) -> Option<[[u8; SIZE]; SIZE]> {
+    let code = code.trim();
+    if code.len() != CELLS || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let mut grid = [[0; SIZE]; SIZE];
+    for (i, b) in code.bytes().enumerate() {
+        grid[i % SIZE][i / SIZE] = b - b'0';
+    }
+    Some(grid)
+}
+
+fn parse_duration(code: &str) -> Option<u64> {
+    // the game subtracts the time from the clock, so it has to be sane
+    code.trim().parse::<u64>().ok().filter(|d| *d <= DURATION_MAX)
+}
+
+fn parse_flags(code: &str) -> Option<[[bool; SIZE]; SIZE]> {
+    if code.len() != CELLS || !code.bytes().all(|b| b == b'0' || b == b'1') {
+        return None;
+    }
+    let mut flags = [[false; SIZE]; SIZE];
+    for (i, b) in code.bytes().enumerate() {
+        flags[i % SIZE][i / SIZE] = b == b'1';
+    }
+    Some(flags)
+}
+
+fn parse_notes(code: &str) -> Option<[[u16; SIZE]; SIZE]> {
+    // every cell has its notes as bits in three hex digits
+    if code.len() != 3 * CELLS || !code.is_ascii() {
+        return None;
+    }
+    let mut notes = [[0; SIZE]; SIZE];
+    for i in 0..CELLS {
+        notes[i % SIZE][i / SIZE] = u16::from_str_radix(&code[i * 3..i * 3 + 3], 16).ok()? & 0x3fe;
+    }
+    Some(notes)
+}
+
+fn grid_string(grid: &[[u8; SIZE]; SIZE]) -> String {
+    let mut result = String::new();
+    for y in 0..SIZE {
+        for col in grid.iter() {
+            result.push((col[y] + b'0') as char);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_puzzles::*;
+
+    fn puzzle_game() -> SaveGame {
+        let mut game = SaveGame {
+            version: SAVE_VERSION,
+            givens: grid(PUZZLE),
+            values: grid(PUZZLE),
+            duration: 95,
+            hints: 1,
+            difficulty: Some(Difficulty::Moderate),
+            seed: Some(1234),
+            history: History::decode("1;200000040001").unwrap(),
+            ..Default::default()
+        };
+        game.values[2][0] = 4;
+        game.valid[2][0] = true;
+        game.notes[3][0] = 0x6;
+        game
+    }
+
+    #[test]
+    fn parse_written_game() {
+        let game = puzzle_game();
+        let parsed = SaveGame::parse(&game.to_text()).unwrap();
+        assert_eq!(parsed.version, SAVE_VERSION);
+        assert_eq!(parsed.givens, game.givens);
+        assert_eq!(parsed.values, game.values);
+        assert_eq!(parsed.valid, game.valid);
+        assert_eq!(parsed.notes, game.notes);
+        assert_eq!(parsed.duration, 95);
+        assert_eq!(parsed.hints, 1);
+        assert_eq!(parsed.difficulty, Some(Difficulty::Moderate));
+        assert_eq!(parsed.seed, Some(1234));
+        assert_eq!(parsed.history.encode(), game.history.encode());
+    }
+
+    #[test]
+    fn parse_v1() {
+        // the first files only know the numbers, so all of them become givens
+        let game = SaveGame::parse(&format!("{},120\n", PUZZLE.replace('.', "0"))).unwrap();
+        assert_eq!(game.version, 1);
+        assert_eq!(game.givens, grid(PUZZLE));
+        assert_eq!(game.values, game.givens);
+        assert_eq!(game.duration, 120);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let digits = PUZZLE.replace('.', "0");
+        assert_eq!(SaveGame::parse("").unwrap_err(), SaveError::Empty);
+        assert_eq!(SaveGame::parse("# only a comment\n\n").unwrap_err(), SaveError::Empty);
+        assert_eq!(SaveGame::parse("format=3\n").unwrap_err(), SaveError::UnsupportedVersion(3));
+        assert_eq!(SaveGame::parse(&format!("givens={}\nvalues={}\n", digits, digits)).unwrap_err(), SaveError::MissingField("format"));
+        assert_eq!(SaveGame::parse(&format!("format=2\nvalues={}\n", digits)).unwrap_err(), SaveError::MissingField("givens"));
+        assert_eq!(SaveGame::parse(&format!("format=2\ngivens={}\n", digits)).unwrap_err(), SaveError::MissingField("values"));
+        assert_eq!(SaveGame::parse("format=2\ngivens=123\n").unwrap_err(), SaveError::InvalidField("givens"));
+        assert_eq!(SaveGame::parse(&format!("{}\n", digits)).unwrap_err(), SaveError::MissingField("duration"));
+        assert_eq!(SaveGame::parse(&format!("{},soon\n", digits)).unwrap_err(), SaveError::InvalidField("duration"));
+        assert_eq!(SaveGame::parse(&format!("{},{}\n", digits, u64::MAX)).unwrap_err(), SaveError::InvalidField("duration"));
+        let text = puzzle_game().to_text().replace("difficulty=Moderate", "difficulty=Impossible");
+        assert_eq!(SaveGame::parse(&text).unwrap_err(), SaveError::InvalidField("difficulty"));
+        let mut game = puzzle_game();
+        game.duration = DURATION_MAX + 1;
+        assert_eq!(SaveGame::parse(&game.to_text()).unwrap_err(), SaveError::InvalidField("duration"));
+    }
+
+    #[test]
+    fn parse_changed_givens() {
+        // a given which differs from the value of its cell
+        let mut game = puzzle_game();
+        game.values[0][0] = 1;
+        assert_eq!(SaveGame::parse(&game.to_text()).unwrap_err(), SaveError::InvalidField("values"));
+    }
+
+}
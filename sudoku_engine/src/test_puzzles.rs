@@ -0,0 +1,28 @@
+// puzzles shared by the unit tests
+
+use crate::board::SudokuBoard;
+use crate::constants::BOARD_SIZE;
+
+// the puzzle of the wikipedia article about sudoku, it has a unique solution
+pub const PUZZLE: &str = "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+pub const SOLUTION: &str = "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+// the cells row by row, '.' is an empty cell
+pub fn grid(code: &str) -> [[u8; BOARD_SIZE as usize]; BOARD_SIZE as usize] {
+    let mut grid = [[0; BOARD_SIZE as usize]; BOARD_SIZE as usize];
+    for (i, c) in code.chars().enumerate() {
+        grid[i % BOARD_SIZE as usize][i / BOARD_SIZE as usize] = c.to_digit(10).unwrap_or(0) as u8;
+    }
+    grid
+}
+
+// all numbers of the code are givens
+pub fn board(code: &str) -> SudokuBoard {
+    let mut board = SudokuBoard::default();
+    for (x, col) in grid(code).iter().enumerate() {
+        for (y, &value) in col.iter().enumerate() {
+            board.set_field(x, y, value);
+        }
+    }
+    board
+}